@@ -17,8 +17,8 @@ where
 }
 
 fn main() {
-    let mut foo = Foo::default();
-    foo.set_private(1);
-    (*foo.private_mut()) += 1;
-    assert_eq!(*foo.private(), 2);
+    let mut val = Foo::default();
+    val.set_private(1);
+    (*val.private_mut()) += 1;
+    assert_eq!(val.private(), 2);
 }
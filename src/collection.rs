@@ -0,0 +1,410 @@
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use proc_macro_error2::abort;
+use syn::{spanned::Spanned, GenericArgument, Index, Member, PathArguments, Type, TypePath};
+
+use crate::generate::{template_name, Access, GenMode, GenParams};
+use crate::model::{FieldModel, ModeAttr};
+
+/// The kind of a collection field, detected from its type.
+enum Collection<'a> {
+    /// `Vec<T>`, `VecDeque<T>` or `LinkedList<T>`, with the method pushing to the back.
+    /// `linked` is set for `LinkedList`, which has no `get` and no stable `retain`.
+    Seq {
+        item: &'a Type,
+        push: Ident,
+        linked: bool,
+    },
+    /// `HashSet<T>` or `BTreeSet<T>`.
+    Set { item: &'a Type, hashed: bool },
+    /// `HashMap<K, V>` or `BTreeMap<K, V>`.
+    Map {
+        key: &'a Type,
+        value: &'a Type,
+        hashed: bool,
+    },
+    /// `String`.
+    Str,
+}
+
+/// A generated method of a collection field.
+struct Method {
+    name: Ident,
+    /// Whether the method takes `&mut self`, i.e. is generated by `collection(write)`.
+    mutates: bool,
+    generics: TokenStream2,
+    args: TokenStream2,
+    ret: TokenStream2,
+    where_clause: TokenStream2,
+    value: TokenStream2,
+}
+
+// Helper function to detect the collection kind of a field, like `extract_option_type`
+fn extract_collection(ty: &Type) -> Option<Collection<'_>> {
+    let Type::Path(TypePath { path, .. }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    let args: Vec<&Type> = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let push = |name| Ident::new(name, Span::call_site());
+
+    match (segment.ident.to_string().as_str(), args.as_slice()) {
+        ("Vec", [item, ..]) => Some(Collection::Seq {
+            item,
+            push: push("push"),
+            linked: false,
+        }),
+        ("VecDeque", [item, ..]) => Some(Collection::Seq {
+            item,
+            push: push("push_back"),
+            linked: false,
+        }),
+        ("LinkedList", [item, ..]) => Some(Collection::Seq {
+            item,
+            push: push("push_back"),
+            linked: true,
+        }),
+        ("HashSet", [item, ..]) => Some(Collection::Set { item, hashed: true }),
+        ("BTreeSet", [item, ..]) => Some(Collection::Set {
+            item,
+            hashed: false,
+        }),
+        ("HashMap", [key, value, ..]) => Some(Collection::Map {
+            key,
+            value,
+            hashed: true,
+        }),
+        ("BTreeMap", [key, value, ..]) => Some(Collection::Map {
+            key,
+            value,
+            hashed: false,
+        }),
+        ("String", []) => Some(Collection::Str),
+        _ => None,
+    }
+}
+
+// Helper function to build the `Q` parameter of a lookup by any borrowed form of the key
+fn lookup_bounds(key: &Type, hashed: bool) -> (TokenStream2, TokenStream2) {
+    let bound = if hashed {
+        quote! { ::std::hash::Hash + ::std::cmp::Eq }
+    } else {
+        quote! { ::std::cmp::Ord }
+    };
+    (
+        quote! { <Q: ?::std::marker::Sized + #bound> },
+        quote! { where #key: ::std::borrow::Borrow<Q> },
+    )
+}
+
+// Helper function to parse `singular = "..."`, defaulting to the field name without a plural `s`
+fn parse_singular(attr: &ModeAttr, base: &str) -> String {
+    match &attr.singular {
+        Some(singular) => singular.clone(),
+        None => {
+            if let Some(stem) = base.strip_suffix("ies") {
+                format!("{}y", stem)
+            } else if base.ends_with('s') && !base.ends_with("ss") {
+                base[..base.len() - 1].to_string()
+            } else {
+                base.to_string()
+            }
+        }
+    }
+}
+
+/// Generates the helpers of a collection field, e.g. `items_at`, `items_iter`, `items_len`,
+/// `push_item`, `extend_items`, `clear_items` and `retain_items` for `items: Vec<Item>`.
+///
+/// `collection(read)` keeps the helpers taking `&self` and `collection(write)` the ones taking
+/// `&mut self`, so a read-only field doesn't expose mutators.
+///
+/// Lookups by key take any borrowed form of the key, like the methods of the maps themselves.
+pub fn implement(model: &FieldModel, params: &GenParams) -> TokenStream2 {
+    let (field, index, access) = (model.field, model.index, &model.access);
+    // Generate nothing for skipped field
+    let Some(attr) = model.attr(params) else {
+        return quote! {};
+    };
+
+    let Some(collection) = extract_collection(&field.ty) else {
+        // 只对字段上的注解报错，struct 上的注解对非集合字段不生效
+        if model.own_attr(params.mode).is_some() {
+            abort!(
+                field.ty.span(),
+                "`collection` is only supported on `Vec`, `VecDeque`, `LinkedList`, `HashSet`, \
+                 `BTreeSet`, `HashMap`, `BTreeMap` and `String` fields"
+            );
+        }
+        return quote! {};
+    };
+    // 只存在于部分枚举变体中的字段不生成
+    if let Access::Enum {
+        exhaustive: false, ..
+    } = access
+    {
+        return quote! {};
+    }
+
+    let field_name = match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(index)),
+    };
+    let place = match access {
+        Access::Struct => quote! { self.#field_name },
+        Access::Enum { .. } => quote! { (*__field) },
+    };
+
+    let base = model.base_name();
+    let singular = parse_singular(attr, &base);
+    let name = |template: &str, base: &str| template_name(template, base, field.span());
+    let name_of = |template: &str| name(template, &base);
+
+    let visibility = attr.visibility();
+    let doc: Vec<_> = field
+        .attrs
+        .iter()
+        .filter(|v| v.meta.path().is_ident("doc"))
+        .collect();
+
+    let method = |name: Ident, mutates, args, ret, value| Method {
+        name,
+        mutates,
+        generics: quote! {},
+        args,
+        ret,
+        where_clause: quote! {},
+        value,
+    };
+    let reader = |name, args, ret, value| method(name, false, args, ret, value);
+    let writer = |name, args, ret, value| method(name, true, args, ret, value);
+    let mutator = |name: &str, args, value| {
+        writer(
+            name_of(name),
+            args,
+            quote! { &mut Self },
+            quote! { #value; self },
+        )
+    };
+    let len = reader(
+        name("{}_len", &base),
+        quote! {},
+        quote! { usize },
+        quote! { #place.len() },
+    );
+    let is_empty = reader(
+        name("{}_is_empty", &base),
+        quote! {},
+        quote! { bool },
+        quote! { #place.is_empty() },
+    );
+    let clear = mutator("clear_{}", quote! {}, quote! { #place.clear() });
+
+    let methods = match collection {
+        Collection::Seq { item, push, linked } => {
+            let mut methods = Vec::new();
+            if !linked {
+                methods.push(reader(
+                    name("{}_at", &base),
+                    quote! { index: usize },
+                    quote! { Option<&#item> },
+                    quote! { #place.get(index) },
+                ));
+            }
+            methods.extend(vec![
+                reader(
+                    name("{}_iter", &base),
+                    quote! {},
+                    quote! { impl Iterator<Item = &#item> },
+                    quote! { #place.iter() },
+                ),
+                len,
+                is_empty,
+                writer(
+                    name("push_{}", &singular),
+                    quote! { item: #item },
+                    quote! { &mut Self },
+                    quote! { #place.#push(item); self },
+                ),
+                mutator(
+                    "extend_{}",
+                    quote! { iter: impl IntoIterator<Item = #item> },
+                    quote! { #place.extend(iter) },
+                ),
+                clear,
+            ]);
+            if !linked {
+                methods.push(mutator(
+                    "retain_{}",
+                    quote! { f: impl FnMut(&#item) -> bool },
+                    quote! { #place.retain(f) },
+                ));
+            }
+            methods
+        }
+        Collection::Set { item, hashed } => {
+            let (generics, where_clause) = lookup_bounds(item, hashed);
+            vec![
+                reader(
+                    name("{}_iter", &base),
+                    quote! {},
+                    quote! { impl Iterator<Item = &#item> },
+                    quote! { #place.iter() },
+                ),
+                len,
+                is_empty,
+                Method {
+                    generics: generics.clone(),
+                    where_clause: where_clause.clone(),
+                    ..reader(
+                        name("{}_contains", &base),
+                        quote! { item: &Q },
+                        quote! { bool },
+                        quote! { #place.contains(item) },
+                    )
+                },
+                writer(
+                    name("insert_{}", &singular),
+                    quote! { item: #item },
+                    quote! { bool },
+                    quote! { #place.insert(item) },
+                ),
+                Method {
+                    generics,
+                    where_clause,
+                    ..writer(
+                        name("remove_{}", &singular),
+                        quote! { item: &Q },
+                        quote! { bool },
+                        quote! { #place.remove(item) },
+                    )
+                },
+                mutator(
+                    "extend_{}",
+                    quote! { iter: impl IntoIterator<Item = #item> },
+                    quote! { #place.extend(iter) },
+                ),
+                clear,
+                mutator(
+                    "retain_{}",
+                    quote! { f: impl FnMut(&#item) -> bool },
+                    quote! { #place.retain(f) },
+                ),
+            ]
+        }
+        Collection::Map { key, value, hashed } => {
+            let (generics, where_clause) = lookup_bounds(key, hashed);
+            vec![
+                Method {
+                    generics: generics.clone(),
+                    where_clause: where_clause.clone(),
+                    ..reader(
+                        name("{}_get", &base),
+                        quote! { key: &Q },
+                        quote! { Option<&#value> },
+                        quote! { #place.get(key) },
+                    )
+                },
+                Method {
+                    generics: generics.clone(),
+                    where_clause: where_clause.clone(),
+                    ..reader(
+                        name("{}_contains", &base),
+                        quote! { key: &Q },
+                        quote! { bool },
+                        quote! { #place.contains_key(key) },
+                    )
+                },
+                reader(
+                    name("{}_iter", &base),
+                    quote! {},
+                    quote! { impl Iterator<Item = (&#key, &#value)> },
+                    quote! { #place.iter() },
+                ),
+                len,
+                is_empty,
+                writer(
+                    name("insert_{}", &singular),
+                    quote! { key: #key, value: #value },
+                    quote! { Option<#value> },
+                    quote! { #place.insert(key, value) },
+                ),
+                Method {
+                    generics,
+                    where_clause,
+                    ..writer(
+                        name("remove_{}", &singular),
+                        quote! { key: &Q },
+                        quote! { Option<#value> },
+                        quote! { #place.remove(key) },
+                    )
+                },
+                mutator(
+                    "extend_{}",
+                    quote! { iter: impl IntoIterator<Item = (#key, #value)> },
+                    quote! { #place.extend(iter) },
+                ),
+                clear,
+                mutator(
+                    "retain_{}",
+                    quote! { f: impl FnMut(&#key, &mut #value) -> bool },
+                    quote! { #place.retain(f) },
+                ),
+            ]
+        }
+        Collection::Str => vec![
+            len,
+            is_empty,
+            mutator("push_{}", quote! { s: &str }, quote! { #place.push_str(s) }),
+            clear,
+            mutator(
+                "retain_{}",
+                quote! { f: impl FnMut(char) -> bool },
+                quote! { #place.retain(f) },
+            ),
+        ],
+    };
+
+    // `collection(read)` 只生成读取的方法，`collection(write)` 只生成修改的方法，默认都生成
+    let both = attr.read == attr.write;
+    let methods = methods.into_iter().filter_map(|method| {
+        if !both && method.mutates != attr.write {
+            return None;
+        }
+        let Method {
+            name,
+            mutates,
+            generics,
+            args,
+            ret,
+            where_clause,
+            value,
+        } = method;
+        let receiver = if mutates {
+            quote! { &mut self }
+        } else {
+            quote! { &self }
+        };
+        let (ret, value) = access.wrap(&field_name, GenMode::Collection, false, ret, value)?;
+        Some(quote! {
+            #(#doc)*
+            #[inline(always)]
+            #visibility fn #name #generics(#receiver, #args) -> #ret #where_clause {
+                #value
+            }
+        })
+    });
+
+    quote! {
+        #(#methods)*
+    }
+}
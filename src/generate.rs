@@ -1,16 +1,90 @@
+use std::rc::Rc;
+
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use proc_macro_error2::abort;
 use syn::{
-    self, ext::IdentExt, spanned::Spanned, Expr, Field, GenericArgument, Lit, Meta, MetaNameValue,
-    PathArguments, Type, TypePath, Visibility,
+    self, spanned::Spanned, Attribute, Expr, Field, GenericArgument, Index, Lit, Member,
+    PathArguments, Type, TypePath,
 };
 
-use self::GenMode::{Get, GetClone, GetCopy, GetMut, Set};
-use super::parse_attr;
+use self::GenMode::{Collection, Get, GetClone, GetCopy, GetMut, Set, Variant, With};
+use crate::model::{FieldModel, ItemConfig, ModeAttr, RetShape, Upgrade};
+use crate::validate;
 
+#[derive(Clone)]
 pub struct GenParams {
     pub mode: GenMode,
-    pub global_attr: Option<Meta>,
+    pub struct_name: Ident,
+    /// The attributes of the struct, parsed once for every mode.
+    pub config: Rc<ItemConfig>,
+    /// Types known to be `Copy` for every field: the struct's `copy_types(...)` and the type
+    /// parameters bounded by `Copy`.
+    pub copy_types: Vec<syn::Path>,
+}
+
+impl GenParams {
+    /// The params another derive would use for the same struct.
+    pub fn with_mode(&self, mode: GenMode) -> GenParams {
+        GenParams {
+            mode,
+            ..self.clone()
+        }
+    }
+}
+
+/// How a generated accessor reaches the field.
+pub enum Access<'a> {
+    /// A field of a struct, accessed as `self.field`.
+    Struct,
+    /// A named field shared by some variants of an enum, accessed through `match self`.
+    /// `exhaustive` is set when every variant has the field.
+    Enum {
+        variants: Vec<&'a Ident>,
+        exhaustive: bool,
+    },
+}
+
+impl Access<'_> {
+    /// Wraps the accessor body in a `match self` for enums.
+    ///
+    /// Fields missing from some variants are returned as `Option`, flattening `Option` fields,
+    /// and get no setter or `with_` method.
+    pub fn wrap(
+        &self,
+        field_name: &Member,
+        mode: GenMode,
+        is_option: bool,
+        ret: TokenStream2,
+        value: TokenStream2,
+    ) -> Option<(TokenStream2, TokenStream2)> {
+        let Access::Enum {
+            variants,
+            exhaustive,
+        } = self
+        else {
+            return Some((ret, value));
+        };
+
+        let arms = quote! { #(Self::#variants { #field_name: __field, .. })|* };
+        // `with` 的方法在 `__this` 上修改字段
+        let scrutinee = if mode == GenMode::With {
+            quote! { &mut __this }
+        } else {
+            quote! { self }
+        };
+        if *exhaustive {
+            Some((ret, quote! { match #scrutinee { #arms => { #value } } }))
+        } else if mode == GenMode::Set || mode == GenMode::With {
+            None
+        } else if is_option {
+            Some((ret, quote! { match self { #arms => #value, _ => None } }))
+        } else {
+            Some((
+                quote! { Option<#ret> },
+                quote! { match self { #arms => Some(#value), _ => None } },
+            ))
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, Copy, Clone)]
@@ -20,9 +94,24 @@ pub enum GenMode {
     GetClone,
     Set,
     GetMut,
+    With,
+    Variant,
+    Collection,
 }
 
 impl GenMode {
+    pub const ALL: [GenMode; 8] = [
+        Get, GetCopy, GetClone, Set, GetMut, With, Variant, Collection,
+    ];
+
+    /// Finds the mode named by an attribute, e.g. `get_copy`.
+    pub fn from_path(path: &syn::Path) -> Option<GenMode> {
+        GenMode::ALL
+            .iter()
+            .copied()
+            .find(|mode| path.is_ident(mode.name()))
+    }
+
     pub fn name(self) -> &'static str {
         match self {
             Get => "get",
@@ -30,247 +119,972 @@ impl GenMode {
             GetClone => "get_clone",
             Set => "set",
             GetMut => "get_mut",
+            With => "with",
+            Variant => "variant",
+            Collection => "collection",
         }
     }
 
     pub fn prefix(self) -> &'static str {
         match self {
-            Get | GetCopy | GetClone | GetMut => "",
+            Get | GetCopy | GetClone | GetMut | Variant | Collection => "",
             Set => "set_",
+            With => "with_",
         }
     }
 
     pub fn suffix(self) -> &'static str {
         match self {
-            Get | GetCopy | GetClone | Set => "",
+            Get | GetCopy | GetClone | Set | With | Variant | Collection => "",
             GetMut => "_mut",
         }
     }
 
-    fn is_get(self) -> bool {
+    pub fn is_get(self) -> bool {
         match self {
             GenMode::Get | GenMode::GetCopy | GenMode::GetClone | GenMode::GetMut => true,
-            GenMode::Set => false,
+            GenMode::Set | GenMode::With | GenMode::Variant | GenMode::Collection => false,
         }
     }
 }
 
 // Helper function to extract string from Expr
-fn expr_to_string(expr: &Expr) -> Option<String> {
-    if let Expr::Lit(expr_lit) = expr {
-        if let Lit::Str(s) = &expr_lit.lit {
-            Some(s.value())
-        } else {
-            None
+// 也接受不带引号的路径，如 `name = foo`、`validate = checks::host`
+pub fn expr_to_string(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(expr_lit) => {
+            if let Lit::Str(s) = &expr_lit.lit {
+                Some(s.value())
+            } else {
+                None
+            }
         }
-    } else {
-        None
-    }
-}
-
-// Helper function to parse visibility
-fn parse_vis_str(s: &str, span: proc_macro2::Span) -> Visibility {
-    match syn::parse_str(s) {
-        Ok(vis) => vis,
-        Err(e) => abort!(span, "Invalid visibility found: {}", e),
+        Expr::Path(expr_path) if expr_path.qself.is_none() => {
+            let path = &expr_path.path;
+            Some(quote!(#path).to_string().replace(' ', ""))
+        }
+        _ => None,
     }
 }
 
-// Helper function to parse visibility attribute
-pub fn parse_visibility(attr: Option<&Meta>, meta_name: &str) -> Option<Visibility> {
-    let meta = attr?;
-    let Meta::NameValue(MetaNameValue { value, path, .. }) = meta else {
-        return None;
+/// Builds a method name from a template where `{}` is replaced by the field name, e.g. `has_{}`.
+///
+/// Names of tuple struct fields would start with a digit and are prefixed with `_`, e.g. `_0_len`.
+pub fn template_name(template: &str, base: &str, span: Span) -> Ident {
+    let name = template.replace("{}", base);
+    let name = if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
     };
 
-    if !path.is_ident(meta_name) {
-        return None;
+    match syn::parse_str(&name) {
+        Ok(ident) => ident,
+        Err(e) => abort!(span, "Invalid name `{}` found: {}", name, e),
     }
+}
 
-    let value_str = expr_to_string(value)?;
-    let vis_str = value_str.split(' ').find(|v| *v != "with_prefix")?;
-
-    Some(parse_vis_str(vis_str, value.span()))
+// Helper function to check if a flag is set on the field itself, to reject it on unsupported types
+fn own_attr_has_flag(field: &FieldModel, params: &GenParams, flag: fn(&ModeAttr) -> bool) -> bool {
+    field.own_attr(params.mode).is_some_and(flag)
 }
 
 /// Some users want legacy/compatibility.
 /// (Getters are often prefixed with `get_`)
-fn has_prefix_attr(f: &Field, params: &GenParams) -> bool {
-    // helper function to check if meta has `with_prefix` attribute
-    let meta_has_prefix = |meta: &Meta| -> bool {
-        if let Meta::NameValue(name_value) = meta {
-            if let Some(s) = expr_to_string(&name_value.value) {
-                return s.split(" ").any(|v| v == "with_prefix");
-            }
-        }
-        false
+fn has_prefix_attr(attr: &ModeAttr, params: &GenParams) -> bool {
+    matches!(params.mode, Get | GetCopy | GetClone) && attr.with_prefix
+}
+
+/// The name of the method generated for the field in the current mode, e.g. `field`,
+/// `get_field`, `set_field`, `field_mut` or `is_field`.
+pub fn method_name(field: &FieldModel, attr: &ModeAttr, params: &GenParams) -> Ident {
+    let base = field.base_name();
+
+    if let Some((template, span)) = attr.name.clone() {
+        // 通过 `name = "..."` 指定了方法名称或模板
+        return template_name(&template, &base, span);
+    }
+    if is_prefix(field, attr, params) {
+        // `get(is_prefix)` 时 bool 字段生成 `is_x`，已经以 `is_`、`has_` 开头的字段保持不变
+        return if base.starts_with("is_") || base.starts_with("has_") {
+            Ident::new(&base, Span::call_site())
+        } else {
+            Ident::new(&format!("is_{}", base), Span::call_site())
+        };
+    }
+
+    // `prefix = "..."`、`suffix = "..."` 替换默认的前缀和后缀
+    let prefix = match attr.prefix.clone() {
+        Some((prefix, _)) => prefix,
+        None => format!(
+            "{}{}",
+            if has_prefix_attr(attr, params) {
+                "get_"
+            } else {
+                ""
+            },
+            params.mode.prefix()
+        ),
     };
+    let suffix = match attr.suffix.clone() {
+        Some((suffix, _)) => suffix,
+        None => format!("{}{}", params.mode.suffix(), combined_suffix(field, params)),
+    };
+    if prefix.is_empty() && suffix.is_empty() && field_name_is_raw(field.field) {
+        return field.field.ident.clone().unwrap();
+    }
 
-    let field_attr_has_prefix = f
-        .attrs
+    // 元组结构体默认生成 `_0`、`_0_mut` 这样的方法名称
+    template_name(
+        &format!("{}{{}}{}", prefix, suffix),
+        &base,
+        field.field.span(),
+    )
+}
+
+// Helper function to name `get_copy` and `get_clone` apart when the field also has a getter by
+// reference, e.g. `name()` and `name_cloned()`
+fn combined_suffix(field: &FieldModel, params: &GenParams) -> &'static str {
+    match params.mode {
+        GetCopy if field.requests(params, Get) => "_copied",
+        GetClone if field.requests(params, Get) || field.requests(params, GetCopy) => "_cloned",
+        _ => "",
+    }
+}
+
+// Helper function to check if `is_prefix` applies, i.e. the getter of a `bool` or `Option<bool>`
+// field asks for it
+fn is_prefix(field: &FieldModel, attr: &ModeAttr, params: &GenParams) -> bool {
+    if !params.mode.is_get() || !params.mode.suffix().is_empty() || !attr.is_prefix {
+        return false;
+    }
+    let ty = extract_option_type(&field.field.ty).unwrap_or(&field.field.ty);
+    let is_bool = matches!(ty, Type::Path(TypePath { path, .. }) if path.is_ident("bool"));
+    if !is_bool && own_attr_has_flag(field, params, |attr| attr.is_prefix) {
+        abort!(
+            field.field.ty.span(),
+            "`is_prefix` is only supported on `bool` and `Option<bool>` fields"
+        );
+    }
+    is_bool
+}
+
+/// A method generated for a field, named once for the conflict checks.
+pub struct MethodName {
+    pub mode: GenMode,
+    /// The position of the field among the fields of the item.
+    pub field: usize,
+    pub name: Ident,
+}
+
+/// The names of the methods generated for the fields in each of the modes, helpers like `has_x`
+/// and `try_set_x` included.
+pub fn method_names(fields: &[FieldModel], modes: &[GenParams]) -> Vec<MethodName> {
+    modes
         .iter()
-        .filter_map(|attr| parse_attr(attr, params.mode))
-        .find(|meta| {
-            meta.path().is_ident("get")
-                || meta.path().is_ident("get_copy")
-                || meta.path().is_ident("get_clone")
+        .flat_map(|params| {
+            fields.iter().enumerate().flat_map(move |(index, field)| {
+                generated_names(field, params)
+                    .into_iter()
+                    .map(move |name| MethodName {
+                        mode: params.mode,
+                        field: index,
+                        name,
+                    })
+            })
         })
-        .as_ref()
-        .map_or(false, meta_has_prefix);
+        .collect()
+}
+
+/// Aborts when two fields generate methods with the same name in the mode, e.g. `is_enabled` for
+/// both `enabled` with `is_prefix` and `is_enabled`, or when a getter of the mode is named like
+/// another getter of the same field.
+pub fn check_name_conflicts(fields: &[FieldModel], names: &[MethodName], mode: GenMode) {
+    let in_mode: Vec<&MethodName> = names.iter().filter(|method| method.mode == mode).collect();
 
-    let global_attr_has_prefix = params.global_attr.as_ref().map_or(false, meta_has_prefix);
+    for (i, method) in in_mode.iter().enumerate() {
+        let field = &fields[method.field];
+        if let Some(other) = in_mode[..i].iter().find(|other| other.name == method.name) {
+            abort!(
+                field.name_span(),
+                "method `{}` is generated for both `{}` and `{}`",
+                method.name,
+                fields[other.field].base_name(),
+                field.base_name()
+            );
+        }
 
-    field_attr_has_prefix || global_attr_has_prefix
+        // 同一字段的 `get`、`get_copy`、`get_clone` 不能生成同名方法
+        if !mode.is_get() {
+            continue;
+        }
+        if let Some(other) = names.iter().find(|other| {
+            other.field == method.field
+                && other.mode != mode
+                && matches!(other.mode, Get | GetCopy | GetClone)
+                && other.name == method.name
+        }) {
+            abort!(
+                field.name_span(),
+                "method `{}` is generated by both `{}` and `{}` for this field",
+                method.name,
+                other.mode.name(),
+                mode.name()
+            );
+        }
+    }
 }
 
-pub fn implement(field: &Field, params: &GenParams) -> TokenStream2 {
-    let field_name = field
-        .ident
-        .clone()
-        .unwrap_or_else(|| abort!(field.span(), "Expected the field to have a name"));
+/// Aborts when accessors of different modes share a name, e.g. `get_mut(suffix = "")` next to
+/// `get`, which `#[derive(Getset)]` can check since it generates every mode at once.
+pub fn check_mode_conflicts(fields: &[FieldModel], names: &[MethodName]) {
+    for (i, method) in names.iter().enumerate() {
+        let Some(other) = names[..i].iter().find(|other| other.name == method.name) else {
+            continue;
+        };
+        abort!(
+            fields[method.field].name_span(),
+            "method `{}` is generated by both `{}` on `{}` and `{}` on `{}`",
+            method.name,
+            other.mode.name(),
+            fields[other.field].base_name(),
+            method.mode.name(),
+            fields[method.field].base_name()
+        );
+    }
+}
 
-    let fn_name = if !has_prefix_attr(field, params)
-        && (params.mode.is_get())
-        && params.mode.suffix().is_empty()
-        && field_name.to_string().starts_with("r#")
-    {
-        field_name.clone()
+/// Warns about fields that get a setter but no getter from `#[derive(Getset)]`.
+///
+/// Proc macros can't emit warnings on stable, so the warning is the use of a deprecated constant
+/// spanned to the field. The `allow` attributes of the item and the field are copied onto it, so
+/// `#[allow(deprecated)]` on either silences the warning.
+pub fn warn_setters_without_getters(
+    item_attrs: &[Attribute],
+    fields: &[FieldModel],
+    names: &[MethodName],
+) -> TokenStream2 {
+    let is_allow = |attr: &&Attribute| attr.path().is_ident("allow");
+    let warnings = fields.iter().enumerate().filter_map(|(index, field)| {
+        let generates = |setter: bool| {
+            names
+                .iter()
+                .any(|method| method.field == index && matches!(method.mode, Set | With) == setter)
+        };
+        if !generates(true) || generates(false) {
+            return None;
+        }
+        let allows = item_attrs
+            .iter()
+            .chain(field.field.attrs.iter())
+            .filter(is_allow);
+        let note = format!(
+            "field `{}` has a setter but no getter, add `#[allow(deprecated)]` to the field if \
+             this is intended",
+            field.base_name()
+        );
+        Some(quote_spanned! {field.name_span()=>
+            #(#allows)*
+            const _: () = {
+                #[deprecated(note = #note)]
+                #[allow(non_upper_case_globals)]
+                const field_has_setter_but_no_getter: () = ();
+                #[allow(dead_code)]
+                fn warn() {
+                    field_has_setter_but_no_getter
+                }
+            };
+        })
+    });
+
+    quote! {
+        #(#warnings)*
+    }
+}
+
+// Helper function to get the names of the methods generated for the field, with its helpers
+fn generated_names(field: &FieldModel, params: &GenParams) -> Vec<Ident> {
+    let Some(attr) = field.attr(params) else {
+        return Vec::new();
+    };
+    // 只存在于部分枚举变体中的字段不会生成 setter 和 `Option` 的辅助方法
+    let partial = matches!(
+        field.access,
+        Access::Enum {
+            exhaustive: false,
+            ..
+        }
+    );
+    if partial && (params.mode == Set || params.mode == With) {
+        return Vec::new();
+    }
+
+    let name = method_name(field, attr, params);
+    let mut names = if params.mode == Set && !attr.checks.is_empty() {
+        vec![try_setter_name(&name)]
     } else {
-        Ident::new(
-            &format!(
-                "{}{}{}{}",
-                if has_prefix_attr(field, params) && (params.mode.is_get()) {
-                    "get_"
-                } else {
-                    ""
-                },
-                params.mode.prefix(),
-                field_name.unraw(),
-                params.mode.suffix()
-            ),
-            Span::call_site(),
-        )
+        vec![name]
     };
+    if let Some(inner_ty) = field_option_type(field, params).filter(|_| !partial) {
+        let helpers = option_helpers(field, attr, params, &inner_ty, &quote! {});
+        names.extend(helpers.into_iter().map(|(name, ..)| name));
+    }
+    if params.mode == Get && attr.smart_ptr {
+        let upgrade = upgrade_helper(field, attr, &field.field.ty, &quote! {});
+        names.extend(upgrade.map(|(name, ..)| name));
+    }
+    names
+}
+
+// Helper function to name the setter of a validated field, e.g. `try_set_port`
+fn try_setter_name(name: &Ident) -> Ident {
+    Ident::new(&format!("try_{}", name), Span::call_site())
+}
+
+// Helper function to get the inner type of an `Option<T>` field, or of a declared alias of it
+fn field_option_type(field: &FieldModel, params: &GenParams) -> Option<Type> {
+    let mut option_types = field.config.option_types.clone();
+    option_types.extend(params.config.option_types.iter().cloned());
+    option_inner_type(&field.field.ty, &option_types)
+}
+
+pub fn implement(model: &FieldModel, params: &GenParams) -> TokenStream2 {
+    let (field, index, access) = (model.field, model.index, &model.access);
+    // 元组结构体的字段没有名称，使用下标访问，如 `self.0`
+    let field_name = match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(index)),
+    };
+
+    // 跳过的字段不生成方法
+    let Some(attr) = model.attr(params) else {
+        return quote! {};
+    };
+
+    let fn_name = method_name(model, attr, params);
     let ty = field.ty.clone(); // 获取字段的类型 生成 ty_get_name 作为 TokenStream
 
     // 判断是否为基础类型？如果是的话将 get 自动转为 getCopy
+    // `copy_types(...)` 声明的类型同样视为 Copy
+    let mut copy_types = model.config.copy_types.clone();
+    copy_types.extend(params.copy_types.iter().cloned());
+    let is_copy = |ty: &Type| check_type_is_copy(ty, &copy_types);
+    // `Option<T>` 的内部类型，包括 `option_types(...)` 声明的别名
+    let option_inner = field_option_type(model, params);
+    let nested_inner = option_inner.as_ref().and_then(extract_option_type);
+    let flatten = attr.flatten;
+    if flatten && nested_inner.is_none() && own_attr_has_flag(model, params, |attr| attr.flatten) {
+        abort!(
+            field.ty.span(),
+            "`flatten` is only supported on `Option<Option<T>>` fields"
+        );
+    }
+    // `get(ret = "...")` 指定返回的形式，此时不再自动转为 getCopy
+    let mut ret_shape = attr.ret;
+    if ret_shape == Some(RetShape::OptionRef) && option_inner.is_none() {
+        // 只对字段上的注解报错，struct 上的 `option_ref` 对非 Option 字段使用默认的返回形式
+        if model
+            .own_attr(params.mode)
+            .is_some_and(|attr| attr.ret == Some(RetShape::OptionRef))
+        {
+            abort!(
+                field.ty.span(),
+                "`ret = \"option_ref\"` is only supported on `Option` fields"
+            );
+        }
+        ret_shape = None;
+    }
+    let ret_type = attr.ret_type.clone();
     let mut mode = params.mode;
     if mode == GenMode::Get
-        && (check_type_is_copy(&field.ty)
-            || extract_option_type(&field.ty)
-                .map_or(false, |inner_ty| check_type_is_copy(inner_ty)))
+        && (ret_shape == Some(RetShape::Copy)
+            || ret_shape.is_none()
+                && ret_type.is_none()
+                && (is_copy(&field.ty) || option_inner.as_ref().is_some_and(is_copy)))
     {
         // 如果当前属性是 copy 类型，那么当 get 时，自动转为 getCopy
         mode = GenMode::GetCopy;
     }
 
-    let ty_get_name = if let Some(inner_ty) = extract_option_type(&ty) {
-        quote! { Option<&#inner_ty> }
+    // 字段的访问方式，结构体为 `self.field`，枚举为 match 中绑定的 `(*__field)`
+    let place = match access {
+        Access::Struct if params.mode == GenMode::With => quote! { __this.#field_name },
+        Access::Struct => quote! { self.#field_name },
+        Access::Enum { .. } => quote! { (*__field) },
+    };
+
+    // `String`、`Vec<T>` 等类型返回 `&str`、`&[T]` 这样的借用形式
+    // `get(smart_ptr)` 时 `Rc<T>`、`Arc<T>` 同样返回 `&T`
+    let deref = !attr.no_deref;
+    let smart_ptr = attr.smart_ptr;
+    let (ty_get_name, ty_get_return) = match (ret_shape, option_inner.as_ref(), nested_inner) {
+        (Some(RetShape::Raw), _, _) => (quote! { &#ty }, quote! { &#place }),
+        (Some(RetShape::OptionRef), Some(inner_ty), _) => {
+            (quote! { Option<&#inner_ty> }, quote! { #place.as_ref() })
+        }
+        // `Option<Option<T>>` 返回 `Option<Option<&T>>`，`get(flatten)` 时返回 `Option<&T>`
+        (None, Some(_), Some(nested_ty)) => {
+            let (nested_ret, nested_value) = match extract_deref_target(nested_ty, smart_ptr, deref)
+            {
+                Some(target) => (
+                    quote! { Option<&#target> },
+                    option_as_deref(nested_ty, smart_ptr, deref, quote! { inner }),
+                ),
+                None => (quote! { Option<&#nested_ty> }, quote! { inner.as_ref() }),
+            };
+            if flatten {
+                (
+                    nested_ret,
+                    quote! { #place.as_ref().and_then(|inner| #nested_value) },
+                )
+            } else {
+                (
+                    quote! { Option<#nested_ret> },
+                    quote! { #place.as_ref().map(|inner| #nested_value) },
+                )
+            }
+        }
+        (None, Some(inner_ty), None) => match extract_deref_target(inner_ty, smart_ptr, deref) {
+            Some(target) => (
+                quote! { Option<&#target> },
+                option_as_deref(inner_ty, smart_ptr, deref, place.clone()),
+            ),
+            None => (quote! { Option<&#inner_ty> }, quote! { #place.as_ref() }),
+        },
+        _ => match extract_deref_target(&ty, smart_ptr, deref) {
+            Some(target) => (quote! { &#target }, quote! { &*#place }),
+            None => (quote! { &#ty }, quote! { &#place }),
+        },
+    };
+
+    // `get_mut(make_mut)` 时 `Arc<T>`、`Rc<T>` 通过 `make_mut` 返回 `&mut T`
+    let make_mut = if params.mode == GenMode::GetMut && attr.make_mut {
+        let make_mut = extract_make_mut(&ty);
+        if make_mut.is_none() && own_attr_has_flag(model, params, |attr| attr.make_mut) {
+            abort!(
+                field.ty.span(),
+                "`make_mut` is only supported on `Arc<T>` and `Rc<T>` fields"
+            );
+        }
+        make_mut
     } else {
-        quote! { &#ty }
+        None
     };
 
-    let ty_get_return = if extract_option_type(&ty).is_some() {
-        quote! { self.#field_name.as_ref() }
+    // `Option<T>` 的 `get_mut` 返回 `Option<&mut T>`，`get_mut(raw)` 时保留 `&mut Option<T>`
+    let raw_mut = params.mode == GenMode::GetMut && attr.raw;
+
+    // setter 的参数以字段命名，元组结构体的字段使用 `val`
+    let param = field
+        .ident
+        .clone()
+        .unwrap_or_else(|| Ident::new("val", Span::call_site()));
+    let into = attr.into;
+    let checks = if params.mode == GenMode::Set {
+        validate::checks(model, attr, &param, params)
     } else {
-        quote! { &self.#field_name }
+        Vec::new()
     };
 
-    let doc = field.attrs.iter().filter(|v| v.meta.path().is_ident("doc"));
+    // `set(some)` 时参数为 `Option<T>` 中的 `T`，赋值时包装为 `Some`
+    let (value_ty, assigned) = match &option_inner {
+        Some(inner_ty) if attr.some => (quote! { #inner_ty }, quote! { Some(#param) }),
+        _ => (quote! { #ty }, quote! { #param }),
+    };
 
-    // 取出是否有 skip
-    let attr = field
+    let doc: Vec<_> = field
         .attrs
         .iter()
-        .filter_map(|v| parse_attr(v, params.mode))
-        .last() // 取出自定义注解
-        .or_else(|| params.global_attr.clone()); // 没有自定义注解时，使用 struct 全局注解
-
-    let visibility = parse_visibility(attr.as_ref(), params.mode.name());
-    // 添加以下代码，当visibility 为 None 时，或为 private ，则设置为 public
-    // 因为 get set 是对外的，没有必要私有，所以默认都为 public。
-    let visibility = match visibility {
-        None | Some(Visibility::Inherited) => Some(Visibility::Public(syn::token::Pub {
-            span: Span::call_site(),
-        })),
-        Some(v) => Some(v),
-    };
-    // 以上是添加代码
-    match attr {
-        // Generate nothing for skipped field
-        Some(meta) if meta.path().is_ident("skip") => {
-            quote! {}
+        .filter(|v| v.meta.path().is_ident("doc"))
+        .collect();
+
+    let visibility = attr.visibility();
+    let (ret, value) = match mode {
+        GenMode::Get => (ty_get_name, ty_get_return),
+        GenMode::GetCopy => match nested_inner {
+            Some(nested_ty) if flatten => {
+                (quote! { Option<#nested_ty> }, quote! { #place.flatten() })
+            }
+            _ => (quote! { #ty }, quote! { #place }),
+        },
+        GenMode::GetClone => (quote! { #ty }, quote! { #place.clone() }),
+        GenMode::Set | GenMode::With => {
+            let convert = if into {
+                quote! { let #param: #value_ty = #param.into(); }
+            } else {
+                quote! {}
+            };
+            let ret = if mode == GenMode::Set {
+                quote! { &mut Self }
+            } else {
+                quote! { Self }
+            };
+            (ret, quote! { #convert #(#checks)* #place = #assigned; })
         }
-        Some(_) => match mode {
-            GenMode::Get => {
-                quote! {
-                    #(#doc)*
-                    #[inline(always)]
-                    #visibility fn #fn_name(&self) -> #ty_get_name {
-                        #ty_get_return
-                    }
+        GenMode::GetMut => match (&make_mut, &option_inner) {
+            (Some((ptr, inner_ty)), _) => (
+                quote! { &mut #inner_ty },
+                quote! { #ptr::make_mut(&mut #place) },
+            ),
+            // `&'a mut T` 字段重新借用为 `&mut T`
+            (None, Some(inner_ty)) if !raw_mut => match extract_mut_ref(inner_ty) {
+                Some(elem) => (
+                    quote! { Option<&mut #elem> },
+                    quote! { #place.as_deref_mut() },
+                ),
+                None => (
+                    quote! { Option<&mut #inner_ty> },
+                    quote! { #place.as_mut() },
+                ),
+            },
+            (None, _) => match extract_mut_ref(&ty) {
+                Some(elem) => (quote! { &mut #elem }, quote! { &mut *#place }),
+                None => (quote! { &mut #ty }, quote! { &mut #place }),
+            },
+        },
+        GenMode::Variant => unreachable!("variant helpers are generated by `variant`"),
+        GenMode::Collection => {
+            unreachable!("collection helpers are generated by `collection`")
+        }
+    };
+    // `ret_type = "..."` 时直接返回字段，依靠 deref 转换为指定的类型
+    let (ret, value) = match &ret_type {
+        Some(ret_ty) if mode == GenMode::GetCopy => (quote! { #ret_ty }, quote! { #place }),
+        Some(ret_ty) => (quote! { #ret_ty }, quote! { &#place }),
+        None => (ret, value),
+    };
+    let is_option = match (&ret_type, ret_shape) {
+        (Some(ret_ty), _) => extract_option_type(ret_ty).is_some(),
+        (None, Some(RetShape::Raw | RetShape::Ref)) => false,
+        (None, _) => option_inner.is_some() && !(mode == GenMode::GetMut && raw_mut),
+    };
+    let Some((ret, value)) = access.wrap(&field_name, mode, is_option, ret, value) else {
+        return quote! {};
+    };
+
+    let param_ty = if into {
+        quote! { impl Into<#value_ty> }
+    } else {
+        quote! { #value_ty }
+    };
+
+    let accessor = match mode {
+        GenMode::Get | GenMode::GetCopy | GenMode::GetClone => {
+            quote! {
+                #(#doc)*
+                #[inline(always)]
+                #visibility fn #fn_name(&self) -> #ret {
+                    #value
                 }
             }
-            GenMode::GetCopy => {
+        }
+        GenMode::Set => {
+            if !checks.is_empty() {
+                // 有校验时生成 `try_set_x`，校验失败时返回错误
+                let try_name = try_setter_name(&fn_name);
+                let error_name = validate::error_name(params);
                 quote! {
                     #(#doc)*
                     #[inline(always)]
-                    #visibility fn #fn_name(&self) -> #ty {
-                        self.#field_name
+                    #visibility fn #try_name(&mut self, #param: #param_ty) -> Result<#ret, #error_name> {
+                        #value
+                        Ok(self)
                     }
                 }
-            }
-            GenMode::GetClone => {
+            } else {
                 quote! {
                     #(#doc)*
                     #[inline(always)]
-                    #visibility fn #fn_name(&self) -> #ty {
-                        self.#field_name.clone()
+                    #visibility fn #fn_name(&mut self, #param: #param_ty) -> #ret {
+                        #value
+                        self
                     }
                 }
             }
-            GenMode::Set => {
+        }
+        GenMode::GetMut => {
+            // `make_mut` 需要在修改前克隆共享的值
+            let where_clause = make_mut.as_ref().map(|(_, inner_ty)| {
                 quote! {
-                    #(#doc)*
-                    #[inline(always)]
-                    #visibility fn #fn_name(&mut self, val: #ty) -> &mut Self {
-                        self.#field_name = val;
-                        self
-                    }
+                    where
+                        #inner_ty: ::std::clone::Clone,
+                }
+            });
+            quote! {
+                #(#doc)*
+                #[inline(always)]
+                #visibility fn #fn_name(&mut self) -> #ret #where_clause {
+                    #value
                 }
             }
-            GenMode::GetMut => {
-                quote! {
+        }
+        GenMode::With if attr.clone => {
+            // 不消耗 self，克隆后返回修改过的新对象
+            quote! {
+                #(#doc)*
+                #[inline(always)]
+                #visibility fn #fn_name(&self, #param: #param_ty) -> #ret
+                where
+                    Self: ::std::clone::Clone,
+                {
+                    let mut __this = ::std::clone::Clone::clone(self);
+                    #value
+                    __this
+                }
+            }
+        }
+        GenMode::With => {
+            quote! {
+                #(#doc)*
+                #[inline(always)]
+                #visibility fn #fn_name(self, #param: #param_ty) -> #ret {
+                    let mut __this = self;
+                    #value
+                    __this
+                }
+            }
+        }
+        GenMode::Variant | GenMode::Collection => unreachable!(),
+    };
+
+    // `Option<T>` 字段可选生成 `take_x`、`has_x` 等辅助方法
+    let helpers = match (access, &option_inner) {
+        (
+            Access::Enum {
+                exhaustive: false, ..
+            },
+            _,
+        )
+        | (_, None) => {
+            check_no_option_flags(model, params);
+            Vec::new()
+        }
+        (_, Some(inner_ty)) => option_helpers(model, attr, params, inner_ty, &place)
+            .into_iter()
+            .filter_map(|(name, receiver, args, ret, value)| {
+                let (ret, value) = access.wrap(&field_name, mode, false, ret, value)?;
+                Some(quote! {
                     #(#doc)*
                     #[inline(always)]
-                    #visibility fn #fn_name(&mut self) -> &mut #ty {
-                        &mut self.#field_name
+                    #visibility fn #name(#receiver #args) -> #ret {
+                        #value
                     }
-                }
-            }
-        },
-        None => quote! {},
-    }
-}
-fn extract_option_type(ty: &Type) -> Option<&Type> {
-    if let Type::Path(TypePath { path, .. }) = ty {
-        if let Some(segment) = path.segments.last() {
-            if segment.ident == "Option" {
-                if let PathArguments::AngleBracketed(ref args) = segment.arguments {
-                    for arg in args.args.iter() {
-                        if let GenericArgument::Type(inner_ty) = arg {
-                            return Some(inner_ty);
+                })
+            })
+            .collect(),
+    };
+
+    // `get(smart_ptr)` 时 `Weak<T>` 字段额外生成 `upgrade_x`
+    let upgrade = if params.mode == GenMode::Get && smart_ptr {
+        upgrade_helper(model, attr, &ty, &place).and_then(|(name, ret, value)| {
+            access
+                .wrap(&field_name, mode, true, ret, value)
+                .map(|(ret, value)| {
+                    quote! {
+                        #(#doc)*
+                        #[inline(always)]
+                        #visibility fn #name(&self) -> #ret {
+                            #value
                         }
                     }
-                }
+                })
+        })
+    } else {
+        None
+    };
+
+    quote! {
+        #accessor
+        #(#helpers)*
+        #upgrade
+    }
+}
+
+// Helper function to generate the opt-in helpers of `Option<T>` fields, as
+// `(name, receiver, extra arguments, return type, body)`
+fn option_helpers(
+    field: &FieldModel,
+    attr: &ModeAttr,
+    params: &GenParams,
+    inner_ty: &Type,
+    place: &TokenStream2,
+) -> Vec<(
+    Ident,
+    TokenStream2,
+    TokenStream2,
+    TokenStream2,
+    TokenStream2,
+)> {
+    let base = field.base_name();
+    let name = |template: &str| template_name(template, &base, field.field.span());
+    let param = field
+        .field
+        .ident
+        .clone()
+        .unwrap_or_else(|| Ident::new("val", Span::call_site()));
+    let mut helpers = Vec::new();
+
+    match params.mode {
+        GenMode::Get if attr.has => helpers.push((
+            name("has_{}"),
+            quote! { &self },
+            quote! {},
+            quote! { bool },
+            quote! { #place.is_some() },
+        )),
+        GenMode::Set => {
+            if attr.clear {
+                helpers.push((
+                    name("clear_{}"),
+                    quote! { &mut self },
+                    quote! {},
+                    quote! { &mut Self },
+                    quote! { #place = None; self },
+                ));
+            }
+            if attr.take {
+                helpers.push((
+                    name("take_{}"),
+                    quote! { &mut self },
+                    quote! {},
+                    quote! { Option<#inner_ty> },
+                    quote! { #place.take() },
+                ));
+            }
+            if attr.replace {
+                helpers.push((
+                    name("replace_{}"),
+                    quote! { &mut self },
+                    quote! { , #param: #inner_ty },
+                    quote! { Option<#inner_ty> },
+                    quote! { #place.replace(#param) },
+                ));
             }
         }
+        GenMode::GetMut if attr.or_insert_with => helpers.push((
+            name("{}_or_insert_with"),
+            quote! { &mut self },
+            quote! { , f: impl FnOnce() -> #inner_ty },
+            quote! { &mut #inner_ty },
+            quote! { #place.get_or_insert_with(f) },
+        )),
+        _ => {}
+    }
+
+    helpers
+}
+
+// Helper function to reject the `Option<T>` helpers on other fields
+fn check_no_option_flags(field: &FieldModel, params: &GenParams) {
+    // 只检查字段上的注解，struct 上的注解对非 Option 字段不生效
+    let Some(own_attr) = field.own_attr(params.mode) else {
+        return;
+    };
+
+    let flags = [
+        ("some", own_attr.some),
+        ("has", own_attr.has),
+        ("clear", own_attr.clear),
+        ("take", own_attr.take),
+        ("replace", own_attr.replace),
+        ("or_insert_with", own_attr.or_insert_with),
+    ];
+    if let Some((flag, _)) = flags.iter().find(|(_, set)| *set) {
+        abort!(
+            field.field.ty.span(),
+            "`{}` is only supported on `Option` fields of structs and of every enum variant",
+            flag
+        );
+    }
+}
+// Helper function to find the borrowed form of well-known owned types, e.g. `str` for `String`,
+// unless `deref` is unset. `&'a mut T` is reborrowed, with the borrowed form of `T` as well
+fn extract_deref_target(ty: &Type, smart_ptr: bool, deref: bool) -> Option<TokenStream2> {
+    // `&'a mut T` 字段重新借用为 `&T`，`&'a mut Vec<u8>` 和 `Vec<u8>` 一样返回 `&[u8]`
+    if let Some(elem) = extract_mut_ref(ty) {
+        return extract_deref_target(elem, smart_ptr, deref).or_else(|| Some(quote! { #elem }));
+    }
+    if !deref {
+        return None;
+    }
+    let Type::Path(TypePath { path, .. }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    let first_arg = || match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }),
+        _ => None,
+    };
+
+    match segment.ident.to_string().as_str() {
+        "String" => Some(quote! { str }),
+        "Vec" => first_arg().map(|inner_ty| quote! { [#inner_ty] }),
+        "Box" => first_arg().map(pointee),
+        "Rc" | "Arc" if smart_ptr => first_arg().map(pointee),
+        "PathBuf" => Some(quote! { ::std::path::Path }),
+        "OsString" => Some(quote! { ::std::ffi::OsStr }),
+        "CString" => Some(quote! { ::std::ffi::CStr }),
+        _ => None,
+    }
+}
+
+// Helper function to borrow an `Option` as `Option<&Target>`, e.g. `Option<&str>` from
+// `Option<String>` or `Option<&'a mut String>`
+fn option_as_deref(
+    inner_ty: &Type,
+    smart_ptr: bool,
+    deref: bool,
+    value: TokenStream2,
+) -> TokenStream2 {
+    // `as_deref` 只能把 `Option<&mut String>` 转为 `Option<&String>`，还需要再解引用一次
+    match extract_mut_ref(inner_ty) {
+        Some(elem) if extract_deref_target(elem, smart_ptr, deref).is_some() => {
+            quote! { #value.as_deref().map(|inner| &**inner) }
+        }
+        _ => quote! { #value.as_deref() },
+    }
+}
+
+// Helper function to quote the pointee of a `Box`, `Rc` or `Arc`, which is borrowed as `&T`
+fn pointee(ty: &Type) -> TokenStream2 {
+    match ty {
+        // `&dyn Handler + Send` 无法解析，需要写成 `&(dyn Handler + Send)`
+        Type::TraitObject(object) if object.bounds.len() > 1 => quote! { (#ty) },
+        _ => quote! { #ty },
+    }
+}
+
+// Helper function to extract `T` from `&'a mut T`
+fn extract_mut_ref(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Reference(reference) if reference.mutability.is_some() => Some(&reference.elem),
+        _ => None,
+    }
+}
+
+// Helper function to find the pointer and pointee of `Arc<T>` and `Rc<T>` fields
+fn extract_make_mut(ty: &Type) -> Option<(TokenStream2, &Type)> {
+    let Type::Path(TypePath { path, .. }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    let ptr = match segment.ident.to_string().as_str() {
+        "Arc" => quote! { ::std::sync::Arc },
+        "Rc" => quote! { ::std::rc::Rc },
+        _ => return None,
+    };
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    let inner_ty = args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })?;
+
+    Some((ptr, inner_ty))
+}
+
+// Helper function to generate `upgrade_x` for `Weak<T>` and `Option<Weak<T>>` fields, as
+// `(name, return type, body)`
+fn upgrade_helper(
+    field: &FieldModel,
+    attr: &ModeAttr,
+    ty: &Type,
+    place: &TokenStream2,
+) -> Option<(Ident, TokenStream2, TokenStream2)> {
+    let (weak_ty, value) = match extract_option_type(ty) {
+        Some(inner_ty) => (
+            inner_ty,
+            quote! { #place.as_ref().and_then(|weak| weak.upgrade()) },
+        ),
+        None => (ty, quote! { #place.upgrade() }),
+    };
+    let Type::Path(TypePath { path, .. }) = weak_ty else {
+        return None;
+    };
+    let segment = path.segments.last().filter(|s| s.ident == "Weak")?;
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    let inner_ty = args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })?;
+
+    // `upgrade = "..."` 指定升级后的指针，否则由路径中的 `rc::`、`sync::` 决定
+    let rc = quote! { ::std::rc::Rc };
+    let arc = quote! { ::std::sync::Arc };
+    let strong = match attr.upgrade {
+        Some(Upgrade::Rc) => rc,
+        Some(Upgrade::Arc) => arc,
+        None if path.segments.iter().any(|s| s.ident == "sync") => arc,
+        None if path.segments.iter().any(|s| s.ident == "rc") => rc,
+        None => abort!(
+            weak_ty.span(),
+            "`Weak` is ambiguous, write `rc::Weak` or `sync::Weak`, or give `upgrade = \"Rc\"` \
+             or `upgrade = \"Arc\"`"
+        ),
+    };
+    let name = template_name("upgrade_{}", &field.base_name(), field.field.span());
+
+    Some((name, quote! { Option<#strong<#inner_ty>> }, value))
+}
+
+// Helper function to check if the field is a raw identifier, e.g. `r#type`
+fn field_name_is_raw(field: &Field) -> bool {
+    field
+        .ident
+        .as_ref()
+        .is_some_and(|ident| ident.to_string().starts_with("r#"))
+}
+
+// Helper function to extract `T` from `Option<T>`, `option::Option<T>`, `std::option::Option<T>`
+// and `core::option::Option<T>`
+fn extract_option_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let mut segments: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+    if segments.len() > 1 && (segments[0] == "std" || segments[0] == "core") {
+        segments.remove(0);
+    }
+    if segments != ["Option"] && segments != ["option", "Option"] {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(args) = &path.segments.last()?.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(inner_ty) => Some(inner_ty),
+        _ => None,
+    })
+}
+
+// Helper function to extract the inner type of `Option<T>` or of a declared alias. The inner type
+// of an alias declared without one is projected as `<Alias as IntoIterator>::Item`
+fn option_inner_type(ty: &Type, option_types: &[(syn::Path, Option<Type>)]) -> Option<Type> {
+    if let Some(inner_ty) = extract_option_type(ty) {
+        return Some(inner_ty.clone());
     }
-    None
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+
+    option_types
+        .iter()
+        .find(|(alias, _)| path_ends_with(path, alias))
+        .map(|(_, inner_ty)| match inner_ty {
+            Some(inner_ty) => inner_ty.clone(),
+            None => syn::parse_quote! { <#ty as ::std::iter::IntoIterator>::Item },
+        })
 }
 
-//
-fn check_type_is_copy(ty: &Type) -> bool {
+// Helper function to check if a type is known to be `Copy`, including the declared `copy_types`
+fn check_type_is_copy(ty: &Type, copy_types: &[syn::Path]) -> bool {
     match ty {
         Type::Path(type_path) => {
             let path = &type_path.path;
@@ -279,21 +1093,160 @@ fn check_type_is_copy(ty: &Type) -> bool {
             }
             let last_segment = path.segments.last().unwrap();
             is_copy_ident(&last_segment.ident)
+                || copy_types
+                    .iter()
+                    .any(|copy_type| path_ends_with(path, copy_type))
+                || is_std_copy(path, copy_types)
         }
-        Type::Array(array_type) => check_type_is_copy(&*array_type.elem),
-        Type::Tuple(tuple_type) => tuple_type.elems.iter().all(check_type_is_copy),
-        Type::Group(group) => check_type_is_copy(&*group.elem),
-        Type::Paren(paren) => check_type_is_copy(&*paren.elem),
+        Type::Reference(reference) => reference.mutability.is_none(), // `&T` 总是 Copy
+        Type::Ptr(_) | Type::Never(_) => true,
+        Type::Array(array_type) => check_type_is_copy(&array_type.elem, copy_types),
+        Type::Tuple(tuple_type) => tuple_type
+            .elems
+            .iter()
+            .all(|elem| check_type_is_copy(elem, copy_types)),
+        Type::Group(group) => check_type_is_copy(&group.elem, copy_types),
+        Type::Paren(paren) => check_type_is_copy(&paren.elem, copy_types),
         Type::BareFn(_) => true, // 函数指针默认实现了 Copy
         _ => false,
     }
 }
 
+// 标准库中总是实现了 Copy 的类型，路径相对于 `std`/`core`
+const STD_COPY_TYPES: &[&str] = &[
+    "time::Duration",
+    "time::Instant",
+    "time::SystemTime",
+    "cmp::Ordering",
+    "sync::atomic::Ordering",
+    "net::IpAddr",
+    "net::Ipv4Addr",
+    "net::Ipv6Addr",
+    "net::SocketAddr",
+    "net::SocketAddrV4",
+    "net::SocketAddrV6",
+    "any::TypeId",
+    "thread::ThreadId",
+    "alloc::Layout",
+    "marker::PhantomData",
+    "marker::PhantomPinned",
+    "ptr::NonNull",
+    "num::NonZero",
+    "num::NonZeroU8",
+    "num::NonZeroU16",
+    "num::NonZeroU32",
+    "num::NonZeroU64",
+    "num::NonZeroU128",
+    "num::NonZeroUsize",
+    "num::NonZeroI8",
+    "num::NonZeroI16",
+    "num::NonZeroI32",
+    "num::NonZeroI64",
+    "num::NonZeroI128",
+    "num::NonZeroIsize",
+];
+
+// 可以不带模块路径使用的标准库类型，其余的需要写成 `alloc::Layout` 这样的路径，
+// 避免和用户自己的同名类型混淆
+const STD_COPY_BARE_NAMES: &[&str] = &[
+    "Duration",
+    "Instant",
+    "SystemTime",
+    "Ordering",
+    "IpAddr",
+    "SocketAddr",
+    "TypeId",
+    "PhantomData",
+    "NonZero",
+    "NonZeroU8",
+    "NonZeroU16",
+    "NonZeroU32",
+    "NonZeroU64",
+    "NonZeroU128",
+    "NonZeroUsize",
+    "NonZeroI8",
+    "NonZeroI16",
+    "NonZeroI32",
+    "NonZeroI64",
+    "NonZeroI128",
+    "NonZeroIsize",
+    "Option",
+];
+
+// 类型参数都为 Copy 时才实现了 Copy 的标准库类型
+const STD_COPY_WRAPPERS: &[&str] = &[
+    "option::Option",
+    "num::Wrapping",
+    "num::Saturating",
+    "cmp::Reverse",
+];
+
+// Helper function to check a path against the std `Copy` tables, accepting `Duration`,
+// `time::Duration`, `std::time::Duration` and `core::time::Duration` alike. Bare names are only
+// accepted for the types of `STD_COPY_BARE_NAMES`
+fn is_std_copy(path: &syn::Path, copy_types: &[syn::Path]) -> bool {
+    let mut segments: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+    if segments.len() == 1 && !STD_COPY_BARE_NAMES.contains(&segments[0].as_str()) {
+        return false;
+    }
+    if segments.len() > 1 && (segments[0] == "std" || segments[0] == "core") {
+        segments.remove(0);
+    }
+    let matches = |std_path: &&str| {
+        let std_segments: Vec<&str> = std_path.split("::").collect();
+        std_segments.len() >= segments.len()
+            && std_segments[std_segments.len() - segments.len()..]
+                .iter()
+                .zip(segments.iter())
+                .all(|(std_segment, segment)| std_segment == segment)
+    };
+
+    if STD_COPY_TYPES.iter().any(matches) {
+        return true;
+    }
+    if !STD_COPY_WRAPPERS.iter().any(matches) {
+        return false;
+    }
+    let PathArguments::AngleBracketed(args) = &path.segments.last().unwrap().arguments else {
+        return false;
+    };
+    args.args.iter().all(|arg| match arg {
+        GenericArgument::Type(ty) => check_type_is_copy(ty, copy_types),
+        _ => true,
+    })
+}
+
+// Helper function to check if a path ends with the segments of another, e.g. `crate::ids::Port`
+// with `Port` or `ids::Port`
+fn path_ends_with(path: &syn::Path, suffix: &syn::Path) -> bool {
+    path.segments.len() >= suffix.segments.len()
+        && path
+            .segments
+            .iter()
+            .rev()
+            .zip(suffix.segments.iter().rev())
+            .all(|(segment, suffix)| segment.ident == suffix.ident)
+}
+
 fn is_copy_ident(ident: &Ident) -> bool {
     // TODO 未能找到直接从类型T获取当前是否实现Copy trait的方法，这里写死了常用的基础数据类型。
-    match ident.to_string().as_str() {
-        "i8" | "i16" | "i32" | "i64" | "i128" | "u8" | "u16" | "u32" | "u64" | "u128" | "isize"
-        | "usize" | "f32" | "f64" | "bool" | "char" | "Copy" => true,
-        _ => false,
-    }
+    matches!(
+        ident.to_string().as_str(),
+        "i8" | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "isize"
+            | "usize"
+            | "f32"
+            | "f64"
+            | "bool"
+            | "char"
+            | "Copy"
+    )
 }
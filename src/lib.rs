@@ -3,12 +3,12 @@ Getset, we're ready to go!
 
 A procedural macro for generating the most basic getters and setters on fields.
 
-Getters are generated as `fn field(&self) -> &type`, while setters are generated as `fn field(&mut self, val: type)`.
+Getters are generated as `fn field(&self) -> &type`, while setters are generated as `fn set_field(&mut self, field: type)`.
 
 These macros are not intended to be used on fields which require custom logic inside of their setters and getters. Just write your own in that case!
 
 ```rust
-use getset::{CopyGetters, Getters, MutGetters, Setters};
+use zyc_getset::{CopyGetters, Getters, MutGetters, Setters};
 
 #[derive(Getters, Setters, MutGetters, CopyGetters, Default)]
 pub struct Foo<T>
@@ -29,13 +29,13 @@ where
 let mut foo = Foo::default();
 foo.set_private(1);
 (*foo.private_mut()) += 1;
-assert_eq!(*foo.private(), 2);
+assert_eq!(foo.private(), 2);
 ```
 
 You can use `cargo-expand` to generate the output. Here are the functions that the above generates (Replicate with `cargo expand --example simple`):
 
 ```rust,ignore
-use getset::{Getters, MutGetters, CopyGetters, Setters};
+use zyc_getset::{Getters, MutGetters, CopyGetters, Setters};
 pub struct Foo<T>
 where
     T: Copy + Clone + Default,
@@ -56,8 +56,8 @@ where
     /// Doc comments are supported!
     /// Multiline, even.
     #[inline(always)]
-    fn private(&self) -> &T {
-        &self.private
+    fn private(&self) -> T {
+        self.private
     }
 }
 impl<T> Foo<T>
@@ -67,8 +67,8 @@ where
     /// Doc comments are supported!
     /// Multiline, even.
     #[inline(always)]
-    pub fn set_public(&mut self, val: T) -> &mut Self {
-        self.public = val;
+    pub fn set_public(&mut self, public: T) -> &mut Self {
+        self.public = public;
         self
     }
 }
@@ -103,11 +103,13 @@ where
 ```
 
 Attributes can be set on struct level for all fields in struct as well. Field level attributes take
-precedence.
+precedence: a field's own attribute for a mode replaces the struct's one as a whole, with its
+visibility and all of its options, so `#[getset(set)]` on a field drops a struct level
+`set(into, non_empty)`.
 
 ```rust
 mod submodule {
-    use getset::{Getters, MutGetters, CopyGetters, Setters};
+    use zyc_getset::{Getters, MutGetters, CopyGetters, Setters};
     #[derive(Getters, CopyGetters, Default)]
     #[getset(get_copy = "pub")] // By default add a pub getting for all fields.
     pub struct Foo {
@@ -125,11 +127,105 @@ let mut foo = submodule::Foo::default();
 foo.public();
 ```
 
+A field can spread its modes over several attributes. Listing some modes skips the struct level
+ones, but never the modes another attribute of the field asks for, while `skip` skips them all.
+
+```rust
+use zyc_getset::{Getters, Setters};
+
+#[derive(Getters, Setters, Default)]
+#[getset(get, set)]
+pub struct Foo {
+    #[getset(get)]
+    #[getset(set(into))]
+    name: String,
+}
+
+let mut foo = Foo::default();
+foo.set_name("foo");
+assert_eq!(foo.name(), "foo");
+```
+
+Each option is only accepted by the modes it applies to, so a misplaced one is an error rather
+than being ignored:
+
+```compile_fail
+use zyc_getset::Getters;
+
+#[derive(Getters)]
+pub struct Foo {
+    #[getset(get(into))] // error: `into` is not supported by `get`
+    name: String,
+}
+```
+
+`Getset` generates every mode requested by the `#[getset(...)]` attributes in a single impl
+block, instead of listing `Getters`, `CopyGetters`, `CloneGetters`, `MutGetters`, `Setters` and
+`WithSetters`. Since it sees all modes at once, it also rejects methods of different modes sharing
+a name and warns about fields with a setter but no getter.
+
+```rust
+use zyc_getset::Getset;
+
+#[derive(Getset, Default)]
+#[getset(get, set)]
+pub struct Foo {
+    id: u64,
+    #[getset(get, get_clone, get_mut)]
+    name: String,
+}
+
+let mut foo = Foo::default();
+foo.set_id(1);
+foo.name_mut().push_str("foo");
+assert_eq!(foo.id(), 1);
+assert_eq!(foo.name_cloned(), "foo");
+```
+
+```compile_fail
+use zyc_getset::Getset;
+
+#[derive(Getset)]
+pub struct Foo {
+    #[getset(get, get_mut(suffix = ""))]
+    name: String, // error: method `name` is generated by both `get` and `get_mut` for this field
+}
+```
+
+The warning about a setter without a getter is reported as the use of a deprecated item, since
+stable Rust has no other way for a derive to warn. It fails to compile under `deny(deprecated)`:
+
+```compile_fail
+#![deny(deprecated)]
+use zyc_getset::Getset;
+
+#[derive(Getset, Default)]
+pub struct Foo {
+    #[getset(set)]
+    secret: u64,
+}
+```
+
+A field meant to be write-only silences the warning with `#[allow(deprecated)]` on the field or
+the struct:
+
+```rust
+#![deny(deprecated)]
+use zyc_getset::Getset;
+
+#[derive(Getset, Default)]
+pub struct Foo {
+    #[getset(set)]
+    #[allow(deprecated)]
+    secret: u64,
+}
+```
+
 For some purposes, it's useful to have the `get_` prefix on the getters for
 either legacy of compatibility reasons. It is done with `with_prefix`.
 
 ```rust
-use getset::{Getters, MutGetters, CopyGetters, Setters};
+use zyc_getset::{Getters, MutGetters, CopyGetters, Setters};
 
 #[derive(Getters, Default)]
 pub struct Foo {
@@ -142,11 +238,452 @@ let mut foo = Foo::default();
 let val = foo.get_field();
 ```
 
+The same options can be given in the list form, with an unquoted visibility in `vis = ...`,
+`with_prefix` as a flag of `get`, `get_copy` and `get_clone`, and unquoted names. Visibilities
+containing spaces, like `pub(in crate::a)`, work in both forms.
+
+```rust
+mod submodule {
+    use zyc_getset::{Getters, Setters};
+
+    #[derive(Getters, Setters, Default)]
+    pub struct Foo {
+        #[getset(get(vis = pub(crate), with_prefix), set(vis = "pub(super)"))]
+        field: bool,
+        #[getset(get(vis = pub(super), name = is_enabled))]
+        enabled: bool,
+    }
+}
+
+let foo = submodule::Foo::default();
+assert!(!foo.get_field());
+assert!(!foo.is_enabled());
+```
+
+`with_prefix` only names getters, so it is rejected on the other modes:
+
+```compile_fail
+use zyc_getset::MutGetters;
+
+#[derive(MutGetters)]
+pub struct Foo {
+    #[getset(get_mut(with_prefix))] // error: `with_prefix` is not supported by `get_mut`
+    field: bool,
+}
+```
+
+Setters can accept anything convertible into the field type with `set(into)`.
+
+```rust
+use zyc_getset::Setters;
+
+#[derive(Setters, Default)]
+#[getset(set(into))]
+pub struct Foo {
+    name: String,
+    parent: Option<String>,
+}
+
+let mut foo = Foo::default();
+foo.set_name("bob").set_parent("alice".to_string());
+```
+
+`Option<T>` fields can opt in to more helpers: `set(some)` makes the setter take `T`, `set(clear)`,
+`set(take)` and `set(replace)` generate `clear_field`, `take_field` and `replace_field`,
+`get(has)` generates `has_field` and `get_mut(or_insert_with)` generates `field_or_insert_with`.
+Mutable getters of `Option<T>` fields return `Option<&mut T>`, or `&mut Option<T>` with
+`get_mut(raw)`.
+
+```rust
+use zyc_getset::{Getters, MutGetters, Setters};
+
+#[derive(Getters, MutGetters, Setters, Default)]
+#[getset(get(has), get_mut, set(some, take))]
+pub struct Foo {
+    name: Option<String>,
+}
+
+let mut foo = Foo::default();
+foo.set_name("bob".to_string());
+foo.name_mut().unwrap().push('!');
+assert_eq!(foo.name(), Some("bob!"));
+foo.set_name("bob".to_string());
+assert!(foo.has_name());
+assert_eq!(foo.take_name(), Some("bob".to_string()));
+assert_eq!(foo.name(), None);
+```
+
+The helpers are checked for name conflicts like the accessors themselves:
+
+```compile_fail
+use zyc_getset::Getters;
+
+#[derive(Getters)]
+#[getset(get)]
+pub struct Foo {
+    #[getset(get(has))]
+    name: Option<String>,
+    has_name: bool, // error: method `has_name` is generated for both `name` and `has_name`
+}
+```
+
+`WithSetters` generates `with_field(self, field: type) -> Self` for chaining on owned values. With
+`with(clone)` they take `&self` instead and return a modified clone.
+
+```rust
+use zyc_getset::{Getters, WithSetters};
+
+#[derive(WithSetters, Getters, Default, Clone)]
+#[getset(get, with(clone))]
+pub struct Config {
+    port: u16,
+}
+
+let base = Config::default();
+let changed = base.with_port(8080);
+assert_eq!(base.port(), 0);
+assert_eq!(changed.port(), 8080);
+```
+
+Setters can validate the value with `range = "..."`, `non_empty` or a custom
+`validate = "path::to::fn"` taking `&T` and returning `Result<(), impl Display>`. Validated fields
+get a `try_set_field` returning a generated `FooSetError` instead of `set_field`.
+
+```rust
+use zyc_getset::Setters;
+
+#[derive(Setters, Default)]
+pub struct Config {
+    #[getset(set(range = "1..=65535"))]
+    port: u32,
+    #[getset(set(non_empty))]
+    name: String,
+}
+
+let mut config = Config::default();
+config.try_set_port(8080).unwrap();
+let err = config.try_set_name(String::new()).err().unwrap();
+assert_eq!(err, ConfigSetError::Name("must not be empty".to_string()));
+assert_eq!(err.field(), "name");
+```
+
+Getters of well-known owned types return their borrowed form: `&str` for `String`, `&[T]` for
+`Vec<T>`, `&Path` for `PathBuf`, `&OsStr` for `OsString`, `&CStr` for `CString` and `&T` for
+`Box<T>`, also inside an `Option`. Use `get(no_deref)` to keep the owned type.
+
+```rust
+use zyc_getset::Getters;
+
+#[derive(Getters, Default)]
+#[getset(get)]
+pub struct Foo {
+    name: String,
+    tags: Option<Vec<String>>,
+    #[getset(get(no_deref))]
+    raw: String,
+}
+
+let foo = Foo::default();
+let name: &str = foo.name();
+let tags: Option<&[String]> = foo.tags();
+let raw: &String = foo.raw();
+```
+
+`CollectionHelpers` generates helpers for collection fields, so the container type stays private:
+`items_iter`, `items_len`, `items_is_empty`, `push_item`, `extend_items`, `clear_items` and
+`retain_items` for `Vec` and `VecDeque`, plus `items_at` for indexed access. `LinkedList` gets
+neither `items_at` nor `retain_items`, since it has no indexed access and no stable `retain`. Sets
+get `tags_contains`, `insert_tag` and `remove_tag`, maps `prices_get`, `prices_contains`,
+`insert_price` and `remove_price`, and `String` fields `push_name`. The singular name drops a
+trailing `s` from the field name, or is given with `collection(singular = "...")`.
+`collection(read)` only generates the helpers taking `&self`, like `items_len` or `prices_get`,
+and `collection(write)` only the ones taking `&mut self`, so a read-only field doesn't expose
+mutators.
+
+```rust
+use std::collections::HashMap;
+use zyc_getset::CollectionHelpers;
+
+#[derive(CollectionHelpers, Default)]
+#[getset(collection)]
+pub struct Inventory {
+    items: Vec<String>,
+    #[getset(collection(singular = "price"))]
+    prices: HashMap<String, u32>,
+}
+
+let mut inventory = Inventory::default();
+inventory
+    .push_item("apple".to_string())
+    .extend_items(vec!["pear".to_string(), "plum".to_string()])
+    .retain_items(|item| item.starts_with('p'));
+inventory.insert_price("pear".to_string(), 3);
+assert_eq!(inventory.items_at(0).map(String::as_str), Some("pear"));
+assert_eq!(inventory.items_len(), 2);
+assert_eq!(inventory.prices_get("pear"), Some(&3));
+assert_eq!(inventory.remove_price("pear"), Some(3));
+assert!(!inventory.prices_contains("pear"));
+```
+
+```rust
+use zyc_getset::CollectionHelpers;
+
+#[derive(CollectionHelpers, Default)]
+pub struct Document {
+    #[getset(collection(read))]
+    pages: Vec<String>,
+}
+
+let document = Document::default();
+assert_eq!(document.pages_len(), 0);
+assert!(document.pages_iter().next().is_none());
+```
+
+With `get(smart_ptr)`, getters also see through `Rc<T>` and `Arc<T>`, returning `&T`, and
+`Weak<T>` fields get an `upgrade_field` returning the strong pointer. `rc::Weak<T>` upgrades to `Rc`
+and `sync::Weak<T>` to `Arc`. A `Weak<T>` imported under its bare name is ambiguous and needs
+`get(smart_ptr, upgrade = "Rc")` or `get(smart_ptr, upgrade = "Arc")`.
+
+```rust
+use std::rc::{self, Rc};
+use std::sync::{Arc, Weak};
+use zyc_getset::Getters;
+
+#[derive(Getters)]
+#[getset(get(smart_ptr))]
+pub struct Node {
+    config: Arc<String>,
+    parent: rc::Weak<String>,
+    #[getset(get(smart_ptr, upgrade = "Arc"))]
+    owner: Weak<String>,
+}
+
+let parent = Rc::new("root".to_string());
+let owner = Arc::new("owner".to_string());
+let node = Node {
+    config: Arc::new("config".to_string()),
+    parent: Rc::downgrade(&parent),
+    owner: Arc::downgrade(&owner),
+};
+let config: &String = node.config();
+assert_eq!(node.upgrade_parent(), Some(parent));
+assert_eq!(node.upgrade_owner(), Some(owner));
+```
+
+`get_mut(make_mut)` on `Arc<T>` and `Rc<T>` fields returns `&mut T` through `make_mut`, cloning
+the value first when it is shared.
+
+```rust
+use std::sync::Arc;
+use zyc_getset::MutGetters;
+
+#[derive(MutGetters, Clone, Default)]
+pub struct Snapshot {
+    #[getset(get_mut(make_mut))]
+    names: Arc<Vec<String>>,
+}
+
+let mut snapshot = Snapshot::default();
+let shared = snapshot.clone();
+snapshot.names_mut().push("foo".to_string());
+assert!(shared.names.is_empty());
+```
+
+Getters of primitive types, of shared references, of well-known `Copy` types of the standard
+library like `Duration`, `Instant`, `IpAddr`, `SocketAddr` or `NonZeroU32`, and of type
+parameters bounded by `Copy` return by value. Less common ones, like `Layout` or `NonNull`, are
+only recognized through a module path such as `alloc::Layout`, so that your own types of the same
+name aren't taken for them. Other
+`Copy` types, like your own newtypes and type aliases, can be declared with `copy_types(...)` on
+the struct or the field to get the same.
+
+```rust
+use zyc_getset::Getters;
+
+type MyInt = i32;
+
+#[derive(Clone, Copy, Default)]
+pub struct Port(u16);
+
+#[derive(Getters, Default)]
+#[getset(get, copy_types(MyInt, Port))]
+pub struct Server {
+    id: MyInt,
+    port: Option<Port>,
+}
+
+let server = Server::default();
+let id: MyInt = server.id();
+let port: Option<Port> = server.port();
+```
+
+The return shape of a getter can be pinned with `get(ret = "...")`: `"ref"` returns `&T` (with
+the borrowed form of `String` and friends), `"copy"` returns `T`, `"option_ref"` returns
+`Option<&T>` and `"raw"` returns `&T` exactly, like upstream getset. On the struct,
+`"option_ref"` only applies to the `Option` fields, the others keep their default shape.
+`get(ret_type = "...")` forces the return type, relying on deref coercion.
+
+```rust
+use std::rc::Rc;
+use zyc_getset::Getters;
+
+#[derive(Getters, Default)]
+pub struct Foo {
+    #[getset(get(ret = "ref"))]
+    count: i32,
+    #[getset(get(ret = "raw"))]
+    parent: Option<String>,
+    #[getset(get(ret_type = "&str"))]
+    title: Rc<str>,
+}
+
+let foo = Foo::default();
+let count: &i32 = foo.count();
+let parent: &Option<String> = foo.parent();
+let title: &str = foo.title();
+```
+
+`Option` is recognized as `Option`, `option::Option`, `std::option::Option` or
+`core::option::Option`. Aliases can be declared with `option_types(...)`, giving the inner type as
+`Alias = "Type"` to benefit from auto-copy and deref. Getters of `Option<Option<T>>` return
+`Option<Option<&T>>`, or `Option<&T>` with `get(flatten)`.
+
+```rust
+use zyc_getset::Getters;
+
+type MaybeId = Option<u64>;
+
+#[derive(Getters, Default)]
+#[getset(get, option_types(MaybeId = "u64"))]
+pub struct Foo {
+    id: MaybeId,
+    nested: Option<Option<String>>,
+    #[getset(get(flatten))]
+    flattened: Option<Option<String>>,
+}
+
+let foo = Foo::default();
+let id: Option<u64> = foo.id();
+let nested: Option<Option<&str>> = foo.nested();
+let flattened: Option<&str> = foo.flattened();
+```
+
+Getters of shared reference fields return them by value with their own lifetime, so the result
+outlives the borrow of the struct. Mutable reference fields are reborrowed: `&'a mut T` gives `&T`
+from `get`, with the borrowed form of `T` like `&[u8]` for `&'a mut Vec<u8>`, and `&mut T` from
+`get_mut`, also inside an `Option`.
+
+```rust
+use zyc_getset::Getters;
+
+#[derive(Getters)]
+#[getset(get)]
+pub struct Token<'a> {
+    name: &'a str,
+    next: Option<&'a str>,
+}
+
+fn name_of<'a>(token: &Token<'a>) -> &'a str {
+    token.name()
+}
+
+let token = Token { name: "foo", next: None };
+assert_eq!(name_of(&token), "foo");
+assert_eq!(token.next(), None);
+```
+
+With `get(is_prefix)`, getters of `bool` and `Option<bool>` fields are named `is_field`, while
+setters keep `set_field`. Fields already starting with `is_` or `has_` keep their name, and two
+fields ending up with the same method name are reported as an error.
+
+```rust
+use zyc_getset::{Getters, Setters};
+
+#[derive(Getters, Setters, Default)]
+#[getset(get(is_prefix), set)]
+pub struct Options {
+    enabled: bool,
+    has_children: bool,
+}
+
+let mut options = Options::default();
+options.set_enabled(true);
+assert!(options.is_enabled());
+assert!(!options.has_children());
+```
+
+```compile_fail
+use zyc_getset::Getters;
+
+#[derive(Getters)]
+#[getset(get(is_prefix))]
+pub struct Options {
+    enabled: bool,
+    is_enabled: bool, // error: method `is_enabled` is generated for both `enabled` and `is_enabled`
+}
+```
+
+Method names can be changed with `name = "..."`, where `{}` stands for the field name, or with
+`prefix = "..."` and `suffix = "..."` replacing the default `set_`, `with_` and `_mut`. On the
+struct they apply to every field, unless the field gives its own attribute for the mode.
+
+```rust
+use zyc_getset::{MutGetters, Setters};
+
+#[derive(Setters, MutGetters, Default)]
+#[getset(set(name = "put_{}"), get_mut(name = "{}_ref_mut"))]
+pub struct Foo {
+    count: u32,
+    #[getset(set(prefix = "assign_"), get_mut)]
+    label: String,
+}
+
+let mut foo = Foo::default();
+foo.put_count(1).assign_label("foo".to_string());
+*foo.count_ref_mut() += 1;
+foo.label_mut().push('!');
+```
+
+`get`, `get_copy` and `get_clone` can be combined on one field. Next to `get`, the copy getter is
+named `<field>_copied` and the clone getter `<field>_cloned`, and the clone getter next to
+`get_copy` is named `<field>_cloned` as well. Accessors that still share a name are rejected.
+
+```rust
+use zyc_getset::{CloneGetters, CopyGetters, Getters};
+
+#[derive(Getters, CopyGetters, CloneGetters, Default)]
+pub struct Foo {
+    #[getset(get, get_clone)]
+    name: String,
+    #[getset(get, get_copy)]
+    id: u64,
+    #[getset(get_copy, get_clone)]
+    count: u32,
+}
+
+let foo = Foo::default();
+let name: &str = foo.name();
+let cloned: String = foo.name_cloned();
+let id: u64 = foo.id_copied();
+let count: u32 = foo.count();
+let count_cloned: u32 = foo.count_cloned();
+```
+
+```compile_fail
+use zyc_getset::{CopyGetters, Getters};
+
+#[derive(Getters, CopyGetters)]
+pub struct Foo {
+    #[getset(get, get_copy(name = "{}"))]
+    id: u64, // error: method `id` is generated by both `get` and `get_copy` for this field
+}
+```
+
 Skipping setters and getters generation for a field when struct level attribute is used
 is possible with `#[getset(skip)]`.
 
 ```rust
-use getset::{CopyGetters, Setters};
+use zyc_getset::{CopyGetters, Setters};
 
 #[derive(CopyGetters, Setters)]
 #[getset(get_copy, set)]
@@ -173,30 +710,116 @@ impl Foo {
     }
 }
 ```
+
+Tuple structs are supported as well. Accessors are named `_0`, `set_0`, `_0_mut` and so on by
+default, or can be named with `name = "..."`.
+
+```rust
+use zyc_getset::{Getters, Setters};
+
+#[derive(Getters, Setters, Default)]
+pub struct UserId(#[getset(get(name = "value"), set(name = "set_value"))] u64);
+
+#[derive(Getters, Default)]
+#[getset(get)]
+pub struct Meters(f64);
+
+let mut id = UserId::default();
+id.set_value(42);
+assert_eq!(id.value(), 42);
+assert_eq!(Meters::default()._0(), 0.0);
+```
+
+On enums, accessors are generated for the named fields of the variants. A field present in every
+variant returns as usual, while a field present in only some variants returns an `Option`.
+Setters are only generated for fields present in every variant.
+
+```rust
+use zyc_getset::{Getters, Setters};
+
+#[derive(Getters, Setters)]
+#[getset(get, set)]
+pub enum Event {
+    Created { id: u64, name: String },
+    Deleted { id: u64, reason: Option<String> },
+}
+
+let mut event = Event::Created { id: 1, name: "foo".to_string() };
+event.set_id(2);
+assert_eq!(event.id(), 2);
+assert_eq!(event.name(), Some("foo"));
+assert_eq!(event.reason(), None);
+```
+
+The attributes of a shared field can be given on one of its occurrences or repeated on several,
+but occurrences with different attributes are rejected:
+
+```compile_fail
+use zyc_getset::Getters;
+
+#[derive(Getters)]
+#[getset(get)]
+pub enum Event {
+    Created {
+        #[getset(get_copy)]
+        id: u64,
+    },
+    Deleted {
+        #[getset(skip)] // error: field `id` has different getset attributes across variants
+        id: u64,
+    },
+}
+```
+
+`VariantHelpers` generates `is_<variant>`, `as_<variant>`, `as_<variant>_mut` and
+`into_<variant>` for each variant of an enum. Variants with several fields are returned as tuples.
+
+```rust
+use zyc_getset::VariantHelpers;
+
+#[derive(VariantHelpers)]
+#[getset(variant)]
+pub enum Shape {
+    Empty,
+    Circle(f64),
+    Rect { width: f64, height: f64 },
+}
+
+let shape = Shape::Rect { width: 1.0, height: 2.0 };
+assert!(shape.is_rect());
+assert!(!shape.is_empty());
+assert_eq!(shape.as_rect(), Some((&1.0, &2.0)));
+assert!(shape.into_circle().is_err());
+```
 */
 
 #[macro_use]
 extern crate quote;
 
+use std::rc::Rc;
+
 use proc_macro::TokenStream;
-use proc_macro2::{Span, TokenStream as TokenStream2};
+use proc_macro2::TokenStream as TokenStream2;
 use proc_macro_error2::{abort, abort_call_site, proc_macro_error};
 use syn::{
-    parse_macro_input, spanned::Spanned, DataStruct, DeriveInput, Meta, MetaNameValue, Token,
+    parse_macro_input, punctuated::Punctuated, spanned::Spanned, DataEnum, DataStruct, DeriveInput,
+    Fields, Ident, Token, Variant,
 };
 
-use crate::generate::{GenMode, GenParams};
+use crate::generate::{Access, GenMode, GenParams};
+use crate::model::{FieldModel, ItemConfig};
 
+mod collection;
 mod generate;
+mod model;
+mod validate;
+mod variant;
 
 #[proc_macro_derive(Getters, attributes(get, with_prefix, getset))]
 #[proc_macro_error]
 pub fn getters(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
-    let params = GenParams {
-        mode: GenMode::Get,
-        global_attr: parse_global_attr(&ast.attrs, GenMode::Get),
-    };
+    let params = gen_params(&ast, GenMode::Get);
     produce(&ast, &params).into()
 }
 
@@ -204,10 +827,7 @@ pub fn getters(input: TokenStream) -> TokenStream {
 #[proc_macro_error]
 pub fn copy_getters(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
-    let params = GenParams {
-        mode: GenMode::GetCopy,
-        global_attr: parse_global_attr(&ast.attrs, GenMode::GetCopy),
-    };
+    let params = gen_params(&ast, GenMode::GetCopy);
     produce(&ast, &params).into()
 }
 
@@ -215,10 +835,7 @@ pub fn copy_getters(input: TokenStream) -> TokenStream {
 #[proc_macro_error]
 pub fn clone_getters(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
-    let params = GenParams {
-        mode: GenMode::GetClone,
-        global_attr: parse_global_attr(&ast.attrs, GenMode::GetClone),
-    };
+    let params = gen_params(&ast, GenMode::GetClone);
     produce(&ast, &params).into()
 }
 
@@ -226,10 +843,7 @@ pub fn clone_getters(input: TokenStream) -> TokenStream {
 #[proc_macro_error]
 pub fn mut_getters(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
-    let params = GenParams {
-        mode: GenMode::GetMut,
-        global_attr: parse_global_attr(&ast.attrs, GenMode::GetMut),
-    };
+    let params = gen_params(&ast, GenMode::GetMut);
     produce(&ast, &params).into()
 }
 
@@ -237,127 +851,296 @@ pub fn mut_getters(input: TokenStream) -> TokenStream {
 #[proc_macro_error]
 pub fn setters(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
-    let params = GenParams {
-        mode: GenMode::Set,
-        global_attr: parse_global_attr(&ast.attrs, GenMode::Set),
-    };
+    let params = gen_params(&ast, GenMode::Set);
     produce(&ast, &params).into()
 }
 
-fn parse_global_attr(attrs: &[syn::Attribute], mode: GenMode) -> Option<Meta> {
-    attrs.iter().filter_map(|v| parse_attr(v, mode)).last()
+#[proc_macro_derive(WithSetters, attributes(with, getset))]
+#[proc_macro_error]
+pub fn with_setters(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let params = gen_params(&ast, GenMode::With);
+    produce(&ast, &params).into()
 }
 
-fn parse_attr(attr: &syn::Attribute, mode: GenMode) -> Option<syn::Meta> {
-    use syn::{punctuated::Punctuated, Token};
+#[proc_macro_derive(
+    Getset,
+    attributes(get, get_copy, get_clone, get_mut, set, with, with_prefix, getset)
+)]
+#[proc_macro_error]
+pub fn getset(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let params = gen_params(&ast, GenMode::Get);
+    produce_all(&ast, &params).into()
+}
 
-    if attr.path().is_ident("getset") {
-        let meta_list =
-            match attr.parse_args_with(Punctuated::<syn::Meta, Token![,]>::parse_terminated) {
-                Ok(list) => list,
-                Err(e) => abort!(attr.span(), "Failed to parse getset attribute: {}", e),
-            };
+#[proc_macro_derive(VariantHelpers, attributes(variant, getset))]
+#[proc_macro_error]
+pub fn variant_helpers(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let params = gen_params(&ast, GenMode::Variant);
+    produce(&ast, &params).into()
+}
 
-        let (last, skip, mut collected) = meta_list
-            .into_iter()
-            .inspect(|meta| {
-                if !(meta.path().is_ident("get")
-                    || meta.path().is_ident("get_copy")
-                    || meta.path().is_ident("get_clone")
-                    || meta.path().is_ident("get_mut")
-                    || meta.path().is_ident("set")
-                    || meta.path().is_ident("skip"))
-                {
-                    abort!(meta.path().span(), "unknown setter or getter")
-                }
-            })
-            .fold(
-                (None, None, Vec::new()),
-                |(last, skip, mut collected), meta| {
-                    // if meta.path().is_ident(mode.name()) {
-                    //     (Some(meta), skip, collected)
-                    // } else if meta.path().is_ident("skip") {
-                    //     (last, Some(meta), collected)
-                    // } else {
-                    //     collected.push(meta);
-                    //     (last, skip, collected)
-                    // }
-                    if meta.path().is_ident(mode.name()) {
-                        // 如果当前 meta 匹配 mode.name()
-                        // 说明在字段配置中找到配置
-                        (Some(meta), skip, collected)
-                    } else if meta.path().is_ident("skip") {
-                        // 如果当前 meta 标识为 "skip"
-                        // 则说明当前字段被标记为 skip
-                        (last, Some(meta), collected)
-                    } else {
-                        collected.push(meta);
-                        (
-                            // 其他情况的话，说明字段进行自定义标注，那么就默认为 skip
-                            last,
-                            Some(Meta::NameValue(MetaNameValue {
-                                path: syn::parse_quote!(skip),
-                                eq_token: syn::token::Eq {
-                                    spans: [Span::call_site()],
-                                },
-                                value: syn::parse_quote!(true),
-                            })),
-                            collected,
-                        )
-                    }
-                },
-            );
+#[proc_macro_derive(CollectionHelpers, attributes(collection, getset))]
+#[proc_macro_error]
+pub fn collection_helpers(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let params = gen_params(&ast, GenMode::Collection);
+    produce(&ast, &params).into()
+}
 
-        if skip.is_some() {
-            // // Check if there is any setter or getter used with skip, which is
-            // // forbidden.
-            // if last.is_none() && collected.is_empty() {
-            //     skip
-            // } else {
-            //     abort!(
-            //         last.or_else(|| collected.pop()).unwrap().path().span(),
-            //         "use of setters and getters with skip is invalid"
-            //     );
-            // }
-            if last.is_some() {
-                last // 说明有多个自定义注解，且其中有一个注解和当前 Mode 相同
-            } else if last.is_none() && collected.is_empty() || 0 == 0 {
-                // 说明需要跳过
-                skip
-            } else {
-                abort!(
-                    last.or_else(|| collected.pop()).unwrap().path().span(),
-                    "use of setters and getters with skip is invalid"
-                );
-            }
-        } else {
-            last
-        }
-    } else if attr.path().is_ident(mode.name()) {
-        // If skip is not used, return the last occurrence of matching
-        // setter/getter, if there is any.
-        attr.meta.clone().into()
-    } else {
-        None
+/// The params of a derive, with the attributes of the struct parsed once for every mode.
+fn gen_params(ast: &DeriveInput, mode: GenMode) -> GenParams {
+    let config = ItemConfig::parse(&ast.attrs);
+    GenParams {
+        mode,
+        struct_name: ast.ident.clone(),
+        copy_types: struct_copy_types(ast, &config),
+        config: Rc::new(config),
     }
 }
 
+/// The types known to be `Copy` for every field: the declared `copy_types(...)` and the type
+/// parameters bounded by `Copy`, inline or in the where clause.
+fn struct_copy_types(ast: &DeriveInput, config: &ItemConfig) -> Vec<syn::Path> {
+    let is_copy_bound = |bound: &syn::TypeParamBound| match bound {
+        syn::TypeParamBound::Trait(bound) => {
+            matches!(bound.modifier, syn::TraitBoundModifier::None)
+                && bound
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|s| s.ident == "Copy")
+        }
+        _ => false,
+    };
+    let type_params: Vec<&Ident> = ast.generics.type_params().map(|p| &p.ident).collect();
+
+    let inline = ast
+        .generics
+        .type_params()
+        .filter(|param| param.bounds.iter().any(is_copy_bound))
+        .map(|param| &param.ident);
+    let where_clause = ast
+        .generics
+        .where_clause
+        .iter()
+        .flat_map(|where_clause| where_clause.predicates.iter())
+        .filter_map(|predicate| match predicate {
+            syn::WherePredicate::Type(predicate) if predicate.bounds.iter().any(is_copy_bound) => {
+                match &predicate.bounded_ty {
+                    syn::Type::Path(ty) if ty.qself.is_none() => ty.path.get_ident(),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .filter(|ident| type_params.contains(ident));
+
+    let mut copy_types = config.copy_types.clone();
+    copy_types.extend(
+        inline
+            .chain(where_clause)
+            .map(|ident| syn::Path::from(ident.clone())),
+    );
+    copy_types
+}
+
 fn produce(ast: &DeriveInput, params: &GenParams) -> TokenStream2 {
     let name = &ast.ident;
     let generics = &ast.generics;
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    // Is it a struct?
-    if let syn::Data::Struct(DataStruct { ref fields, .. }) = ast.data {
-        let generated = fields.iter().map(|f| generate::implement(f, params));
+    if params.mode == GenMode::Variant {
+        let syn::Data::Enum(DataEnum { ref variants, .. }) = ast.data else {
+            abort_call_site!("#[derive(VariantHelpers)] is only defined for enums!")
+        };
+        let generated = variants
+            .iter()
+            .map(|v| variant::implement(v, variants.len() == 1, params));
 
-        quote! {
+        return quote! {
             impl #impl_generics #name #ty_generics #where_clause {
                 #(#generated)*
             }
+        };
+    }
+
+    let fields = collect_fields(ast);
+
+    if params.mode != GenMode::Collection {
+        // getter 的名称还要和同一字段的其他 getter 比较
+        let mut modes = vec![params.clone()];
+        if params.mode.is_get() {
+            modes.extend(
+                [GenMode::Get, GenMode::GetCopy, GenMode::GetClone]
+                    .iter()
+                    .filter(|mode| **mode != params.mode)
+                    .map(|mode| params.with_mode(*mode)),
+            );
         }
+        let names = generate::method_names(&fields, &modes);
+        generate::check_name_conflicts(&fields, &names, params.mode);
+    }
+
+    let generated = fields.iter().map(|field| match params.mode {
+        GenMode::Collection => collection::implement(field, params),
+        _ => generate::implement(field, params),
+    });
+
+    // 有校验的 setter 需要生成对应的错误类型
+    let set_error = if params.mode == GenMode::Set {
+        validate::implement_error(&ast.vis, &fields, params)
     } else {
-        // Nope. This is an Enum. We cannot handle these!
-        abort_call_site!("#[derive(Getters)] is only defined for structs, not for enums!");
+        quote! {}
+    };
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#generated)*
+        }
+
+        #set_error
+    }
+}
+
+/// Generates the accessors of every mode in a single impl block, for `#[derive(Getset)]`.
+fn produce_all(ast: &DeriveInput, params: &GenParams) -> TokenStream2 {
+    let name = &ast.ident;
+    let generics = &ast.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let fields = collect_fields(ast);
+    let modes: Vec<GenParams> = [
+        GenMode::Get,
+        GenMode::GetCopy,
+        GenMode::GetClone,
+        GenMode::GetMut,
+        GenMode::Set,
+        GenMode::With,
+    ]
+    .iter()
+    .map(|mode| params.with_mode(*mode))
+    .collect();
+
+    let names = generate::method_names(&fields, &modes);
+    for params in &modes {
+        generate::check_name_conflicts(&fields, &names, params.mode);
+    }
+    generate::check_mode_conflicts(&fields, &names);
+    let warnings = generate::warn_setters_without_getters(&ast.attrs, &fields, &names);
+
+    let generated = modes.iter().flat_map(|params| {
+        fields
+            .iter()
+            .map(move |field| generate::implement(field, params))
+    });
+    let set_error = modes
+        .iter()
+        .filter(|params| params.mode == GenMode::Set)
+        .map(|params| validate::implement_error(&ast.vis, &fields, params));
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#generated)*
+        }
+
+        #(#set_error)*
+
+        #warnings
     }
 }
+
+// Helper function to collect the fields of a struct, or the named fields shared by enum variants
+fn collect_fields(ast: &DeriveInput) -> Vec<FieldModel<'_>> {
+    match ast.data {
+        syn::Data::Struct(DataStruct { ref fields, .. }) => fields
+            .iter()
+            .enumerate()
+            .map(|(index, f)| FieldModel::new(f, index, Access::Struct))
+            .collect(),
+        syn::Data::Enum(DataEnum { ref variants, .. }) => shared_fields(variants)
+            .into_iter()
+            .map(|(f, variant_names)| {
+                let access = Access::Enum {
+                    exhaustive: variant_names.len() == variants.len(),
+                    variants: variant_names,
+                };
+                FieldModel::new(f, 0, access)
+            })
+            .collect(),
+        syn::Data::Union(_) => {
+            abort_call_site!(
+                "#[derive(Getters)] is only defined for structs and enums, not for unions!"
+            )
+        }
+    }
+}
+
+/// Groups the named fields of an enum by name, in order of first appearance, together with the
+/// variants that have them.
+///
+/// Attributes and doc comments are taken from the first occurrence that is annotated. Other
+/// occurrences may repeat the same getset attributes, but not give different ones.
+fn shared_fields(variants: &Punctuated<Variant, Token![,]>) -> Vec<(&syn::Field, Vec<&Ident>)> {
+    let mut shared: Vec<(&syn::Field, Vec<&Ident>)> = Vec::new();
+
+    for variant in variants {
+        let Fields::Named(ref fields) = variant.fields else {
+            continue;
+        };
+        for field in fields.named.iter() {
+            let Some(entry) = shared.iter_mut().find(|(f, _)| f.ident == field.ident) else {
+                shared.push((field, vec![&variant.ident]));
+                continue;
+            };
+
+            let (ty, other_ty) = (&entry.0.ty, &field.ty);
+            if quote!(#ty).to_string() != quote!(#other_ty).to_string() {
+                abort!(
+                    field.ty.span(),
+                    "field `{}` has different types across variants",
+                    field.ident.as_ref().unwrap()
+                );
+            }
+            let (attrs, other_attrs) = (getset_attrs(entry.0), getset_attrs(field));
+            if attrs.is_empty() {
+                entry.0 = field;
+            } else if !other_attrs.is_empty() && attrs != other_attrs {
+                let ident = field.ident.as_ref().unwrap();
+                abort!(
+                    ident.span(),
+                    "field `{}` has different getset attributes across variants",
+                    ident
+                );
+            }
+            entry.1.push(&variant.ident);
+        }
+    }
+
+    shared
+}
+
+// Helper function to collect the getset attributes of a field as strings, to compare them
+fn getset_attrs(field: &syn::Field) -> Vec<String> {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| {
+            [
+                "getset",
+                "get",
+                "get_copy",
+                "get_clone",
+                "get_mut",
+                "set",
+                "with",
+                "collection",
+            ]
+            .iter()
+            .any(|name| attr.path().is_ident(name))
+        })
+        .map(|attr| quote!(#attr).to_string())
+        .collect()
+}
@@ -0,0 +1,497 @@
+use proc_macro2::Span;
+use proc_macro_error2::abort;
+use syn::{
+    ext::IdentExt, parse::ParseStream, punctuated::Punctuated, spanned::Spanned, Attribute, Field,
+    Ident, LitStr, Meta, MetaNameValue, Path, Token, Type, Visibility,
+};
+
+use crate::generate::GenMode::{Collection, Get, GetClone, GetCopy, GetMut, Set, With};
+use crate::generate::{expr_to_string, Access, GenMode, GenParams};
+use crate::validate::{self, Check};
+
+/// The modes of the accessors named with `name`, `prefix` and `suffix`.
+const NAMED: &[GenMode] = &[Get, GetCopy, GetClone, Set, GetMut, With];
+
+/// The options of the list form and the modes accepting them, e.g. `into` in `set(into)`.
+const OPTIONS: &[(&str, &[GenMode])] = &[
+    ("name", NAMED),
+    ("prefix", NAMED),
+    ("suffix", NAMED),
+    ("with_prefix", &[Get, GetCopy, GetClone]),
+    ("no_deref", &[Get]),
+    ("into", &[Set, With]),
+    ("validate", &[Set]),
+    ("range", &[Set]),
+    ("non_empty", &[Set]),
+    ("clone", &[With]),
+    ("some", &[Set, With]),
+    ("has", &[Get]),
+    ("clear", &[Set]),
+    ("take", &[Set]),
+    ("replace", &[Set]),
+    ("or_insert_with", &[GetMut]),
+    ("singular", &[Collection]),
+    ("read", &[Collection]),
+    ("write", &[Collection]),
+    ("smart_ptr", &[Get]),
+    ("upgrade", &[Get]),
+    ("make_mut", &[GetMut]),
+    ("raw", &[GetMut]),
+    ("ret", &[Get]),
+    ("ret_type", &[Get]),
+    ("flatten", &[Get, GetCopy]),
+    ("is_prefix", &[Get, GetCopy, GetClone]),
+];
+
+/// The return shape of a getter forced with `get(ret = "...")`.
+#[derive(PartialEq, Eq, Copy, Clone)]
+pub enum RetShape {
+    /// `&T`, with the borrowed form of well-known owned types, and `&Option<T>` for options.
+    Ref,
+    /// `T`, as with `get_copy`.
+    Copy,
+    /// `Option<&T>` for `Option<T>` fields.
+    OptionRef,
+    /// `&T` exactly.
+    Raw,
+}
+
+/// The strong pointer a `Weak<T>` field is upgraded to, given with `get(upgrade = "...")`.
+#[derive(PartialEq, Eq, Copy, Clone)]
+pub enum Upgrade {
+    Rc,
+    Arc,
+}
+
+/// A mode as requested by an attribute, e.g. `get = "pub with_prefix"` or `set(into)`, with the
+/// options of the list form parsed for the mode.
+#[derive(Clone, Default)]
+pub struct ModeAttr {
+    /// The visibility given by the attribute, e.g. `pub(crate)` in `get = "pub(crate)"` or
+    /// `get(vis = pub(crate))`.
+    vis: Option<Visibility>,
+    /// Whether `with_prefix` is given, e.g. `get = "pub with_prefix"` or `get(with_prefix)`.
+    pub with_prefix: bool,
+    /// The method name or a template of it, e.g. `name = "put_{}"`, with its span.
+    pub name: Option<(String, Span)>,
+    /// The prefix replacing the default one, e.g. `prefix = "assign_"`.
+    pub prefix: Option<(String, Span)>,
+    /// The suffix replacing the default one, e.g. `suffix = "_ref"`.
+    pub suffix: Option<(String, Span)>,
+    pub no_deref: bool,
+    pub into: bool,
+    /// The checks of a validated setter, in the order given.
+    pub checks: Vec<Check>,
+    pub clone: bool,
+    pub some: bool,
+    pub has: bool,
+    pub clear: bool,
+    pub take: bool,
+    pub replace: bool,
+    pub or_insert_with: bool,
+    pub singular: Option<String>,
+    /// Whether `collection(read)` asks for the helpers reading the collection.
+    pub read: bool,
+    /// Whether `collection(write)` asks for the helpers modifying the collection.
+    pub write: bool,
+    pub smart_ptr: bool,
+    pub upgrade: Option<Upgrade>,
+    pub make_mut: bool,
+    pub raw: bool,
+    pub ret: Option<RetShape>,
+    pub ret_type: Option<Type>,
+    pub flatten: bool,
+    pub is_prefix: bool,
+}
+
+impl ModeAttr {
+    // Helper function to parse `get`, `get = "pub with_prefix"` or `get(vis = pub, name = "value")`
+    fn parse(meta: &Meta, mode: GenMode) -> ModeAttr {
+        match meta {
+            Meta::Path(_) => ModeAttr::default(),
+            Meta::NameValue(MetaNameValue { value, .. }) => {
+                let Some(value_str) = expr_to_string(value) else {
+                    return ModeAttr::default();
+                };
+                let (markers, vis): (Vec<&str>, Vec<&str>) = value_str
+                    .split_whitespace()
+                    .partition(|token| *token == "with_prefix");
+                // 可见性中可能有空格，如 `pub(in crate::a)`
+                let vis = if vis.is_empty() {
+                    None
+                } else {
+                    Some(parse_vis_str(&vis.join(" "), value.span()))
+                };
+                ModeAttr {
+                    vis,
+                    with_prefix: !markers.is_empty(),
+                    ..ModeAttr::default()
+                }
+            }
+            Meta::List(list) => {
+                let entries = match list.parse_args_with(parse_list_entries) {
+                    Ok(entries) => entries,
+                    Err(e) => abort!(e.span(), "Failed to parse getset attribute: {}", e),
+                };
+
+                let mut attr = ModeAttr::default();
+                for entry in entries {
+                    match entry {
+                        ListEntry::Vis(vis) => attr.vis = Some(vis),
+                        ListEntry::Option(option) => attr.parse_option(&option, mode),
+                    }
+                }
+                attr
+            }
+        }
+    }
+
+    // Helper function to parse an option of the list form, rejecting the ones the mode ignores
+    fn parse_option(&mut self, option: &Meta, mode: GenMode) {
+        let path = option.path();
+        let Some((name, modes)) = OPTIONS.iter().find(|(name, _)| path.is_ident(name)) else {
+            abort!(path.span(), "unknown getset option")
+        };
+        if !modes.contains(&mode) {
+            abort!(
+                path.span(),
+                "`{}` is not supported by `{}`",
+                name,
+                mode.name()
+            );
+        }
+
+        let flag = || {
+            if !matches!(option, Meta::Path(_)) {
+                abort!(option.span(), "Expected `{}` without a value", name);
+            }
+            true
+        };
+        match *name {
+            "name" => self.name = Some(parse_str(option)),
+            "prefix" => self.prefix = Some(parse_str(option)),
+            "suffix" => self.suffix = Some(parse_str(option)),
+            // `get(with_prefix)` 等同于 `get = "with_prefix"`
+            "with_prefix" => self.with_prefix = flag(),
+            "no_deref" => self.no_deref = flag(),
+            "into" => self.into = flag(),
+            "validate" | "range" | "non_empty" => self.checks.push(validate::parse_check(option)),
+            "clone" => self.clone = flag(),
+            "some" => self.some = flag(),
+            "has" => self.has = flag(),
+            "clear" => self.clear = flag(),
+            "take" => self.take = flag(),
+            "replace" => self.replace = flag(),
+            "or_insert_with" => self.or_insert_with = flag(),
+            "singular" => self.singular = Some(parse_str(option).0),
+            "read" => self.read = flag(),
+            "write" => self.write = flag(),
+            "smart_ptr" => self.smart_ptr = flag(),
+            "upgrade" => {
+                self.upgrade = match parse_str(option) {
+                    (ptr, _) if ptr == "Rc" => Some(Upgrade::Rc),
+                    (ptr, _) if ptr == "Arc" => Some(Upgrade::Arc),
+                    (_, span) => {
+                        abort!(span, "Expected `upgrade = \"Rc\"` or `upgrade = \"Arc\"`")
+                    }
+                }
+            }
+            "make_mut" => self.make_mut = flag(),
+            "raw" => self.raw = flag(),
+            "ret" => {
+                self.ret = match parse_str(option) {
+                    (shape, _) if shape == "ref" => Some(RetShape::Ref),
+                    (shape, _) if shape == "copy" => Some(RetShape::Copy),
+                    (shape, _) if shape == "option_ref" => Some(RetShape::OptionRef),
+                    (shape, _) if shape == "raw" => Some(RetShape::Raw),
+                    (_, span) => abort!(
+                        span,
+                        "Expected `ret = \"ref\"`, `\"copy\"`, `\"option_ref\"` or `\"raw\"`"
+                    ),
+                }
+            }
+            "ret_type" => self.ret_type = Some(validate::parse_option_str(option)),
+            "flatten" => self.flatten = flag(),
+            "is_prefix" => self.is_prefix = flag(),
+            _ => unreachable!("every option of `OPTIONS` is parsed"),
+        }
+    }
+
+    /// The visibility of the generated methods, `pub` unless the attribute gives another one.
+    pub fn visibility(&self) -> Visibility {
+        // 因为 get set 是对外的，没有必要私有，所以默认都为 public
+        match &self.vis {
+            None | Some(Visibility::Inherited) => Visibility::Public(syn::token::Pub {
+                span: Span::call_site(),
+            }),
+            Some(vis) => vis.clone(),
+        }
+    }
+}
+
+// Helper function to parse a string option, e.g. `name = "..."`, with the span of its value
+fn parse_str(option: &Meta) -> (String, Span) {
+    let name = option.path().get_ident().map(ToString::to_string);
+    let name = name.as_deref().unwrap_or_default();
+    let Meta::NameValue(MetaNameValue { value, .. }) = option else {
+        abort!(option.span(), "Expected `{} = \"...\"`", name);
+    };
+    let Some(s) = expr_to_string(value) else {
+        abort!(value.span(), "Expected `{} = \"...\"`", name);
+    };
+    (s, value.span())
+}
+
+/// An entry of the list form of a mode, e.g. `vis = pub(crate)` or `name = "value"`.
+enum ListEntry {
+    Vis(Visibility),
+    Option(Meta),
+}
+
+// Helper function to parse the entries of the list form. `vis` takes a visibility, unquoted as in
+// `vis = pub(in super::other)` or quoted as in `vis = "pub(crate)"`, which isn't an expression
+fn parse_list_entries(input: ParseStream) -> syn::Result<Vec<ListEntry>> {
+    let mut entries = Vec::new();
+    while !input.is_empty() {
+        let fork = input.fork();
+        let is_vis = fork
+            .call(Ident::parse_any)
+            .is_ok_and(|ident| ident == "vis")
+            && fork.peek(Token![=]);
+        if is_vis {
+            input.call(Ident::parse_any)?;
+            input.parse::<Token![=]>()?;
+            let vis = if input.peek(LitStr) {
+                input.parse::<LitStr>()?.parse()?
+            } else {
+                input.parse()?
+            };
+            entries.push(ListEntry::Vis(vis));
+        } else {
+            entries.push(ListEntry::Option(input.parse()?));
+        }
+
+        if input.is_empty() {
+            break;
+        }
+        input.parse::<Token![,]>()?;
+    }
+    Ok(entries)
+}
+
+// Helper function to parse visibility
+fn parse_vis_str(s: &str, span: Span) -> Visibility {
+    match syn::parse_str(s) {
+        Ok(vis) => vis,
+        Err(e) => abort!(span, "Invalid visibility found: {}", e),
+    }
+}
+
+/// What the attributes of an item decide for a mode.
+#[derive(Clone)]
+pub enum Decision {
+    /// The item asks for the mode.
+    Mode(Box<ModeAttr>),
+    /// The item is skipped, with `skip` or by asking for other modes only.
+    Skip,
+}
+
+/// The `#[getset(...)]` attributes of a struct, field or variant, parsed once.
+#[derive(Clone, Default)]
+pub struct ItemConfig {
+    decisions: Vec<(GenMode, Decision)>,
+    /// Types declared as `Copy` with `copy_types(...)`.
+    pub copy_types: Vec<Path>,
+    /// Aliases of `Option` declared with `option_types(...)`, with their inner type when given.
+    pub option_types: Vec<(Path, Option<Type>)>,
+}
+
+impl ItemConfig {
+    /// Parses the attributes of an item. A mode requested or skipped by a later attribute takes
+    /// precedence, while listing other modes only skips the modes no attribute asks for.
+    pub fn parse(attrs: &[Attribute]) -> ItemConfig {
+        let mut config = ItemConfig::default();
+        for attr in attrs {
+            if attr.path().is_ident("getset") {
+                config.parse_getset(attr);
+            } else if let Some(mode) = GenMode::from_path(attr.path()) {
+                // 单独的注解，如 `#[get = "pub"]`，只影响对应的 mode
+                config.decide(
+                    mode,
+                    Decision::Mode(Box::new(ModeAttr::parse(&attr.meta, mode))),
+                );
+            }
+        }
+        config
+    }
+
+    // Helper function to parse `#[getset(get, set(into), skip, copy_types(...))]`
+    fn parse_getset(&mut self, attr: &Attribute) {
+        let meta_list = match attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+        {
+            Ok(list) => list,
+            Err(e) => abort!(attr.span(), "Failed to parse getset attribute: {}", e),
+        };
+
+        let mut requested: Vec<(GenMode, ModeAttr)> = Vec::new();
+        let mut skip = false;
+        for meta in meta_list {
+            if meta.path().is_ident("copy_types") {
+                // 类型声明不是生成方法的注解，不影响 skip 的判断
+                self.copy_types.extend(parse_copy_types(&meta));
+            } else if meta.path().is_ident("option_types") {
+                self.option_types.extend(parse_option_types(&meta));
+            } else if meta.path().is_ident("skip") {
+                skip = true;
+            } else if let Some(mode) = GenMode::from_path(meta.path()) {
+                requested.push((mode, ModeAttr::parse(&meta, mode)));
+            } else {
+                abort!(meta.path().span(), "unknown setter or getter")
+            }
+        }
+
+        for mode in GenMode::ALL.iter().copied() {
+            match requested.iter().rev().find(|(other, _)| *other == mode) {
+                Some((_, attr)) => self.decide(mode, Decision::Mode(Box::new(attr.clone()))),
+                None if skip => self.decide(mode, Decision::Skip),
+                // 字段进行了自定义标注时，没有标注的 mode 默认为 skip，
+                // 但不覆盖其他注解中明确标注的 mode
+                None if !requested.is_empty() && self.decision(mode).is_none() => {
+                    self.decide(mode, Decision::Skip)
+                }
+                None => {}
+            }
+        }
+    }
+
+    // Helper function to record a decision, replacing the one of an earlier attribute
+    fn decide(&mut self, mode: GenMode, decision: Decision) {
+        self.decisions.retain(|(other, _)| *other != mode);
+        self.decisions.push((mode, decision));
+    }
+
+    /// The decision of the item for the mode, if its attributes make one.
+    pub fn decision(&self, mode: GenMode) -> Option<&Decision> {
+        self.decisions
+            .iter()
+            .find(|(other, _)| *other == mode)
+            .map(|(_, decision)| decision)
+    }
+
+    /// The attribute of the item requesting the mode.
+    pub fn mode(&self, mode: GenMode) -> Option<&ModeAttr> {
+        match self.decision(mode) {
+            Some(Decision::Mode(attr)) => Some(attr.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Collects the entries of a type list like `copy_types(MyInt, Port)`.
+fn parse_type_list(meta: &Meta) -> Vec<Meta> {
+    let name = meta.path().get_ident().map(ToString::to_string);
+    let name = name.as_deref().unwrap_or_default();
+    let Meta::List(list) = meta else {
+        abort!(meta.span(), "Expected `{}(Type, ...)`", name);
+    };
+    match list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
+        Ok(entries) => entries.into_iter().collect(),
+        Err(e) => abort!(list.span(), "Failed to parse {}: {}", name, e),
+    }
+}
+
+/// Collects the types declared as `Copy` with `copy_types(MyInt, Port)`.
+fn parse_copy_types(meta: &Meta) -> Vec<Path> {
+    parse_type_list(meta)
+        .into_iter()
+        .map(|entry| match entry {
+            Meta::Path(path) => path,
+            entry => abort!(entry.span(), "Expected `copy_types(Type, ...)`"),
+        })
+        .collect()
+}
+
+/// Collects the aliases of `Option` declared with `option_types(MaybeId, Name = "String")`, with
+/// their inner type when given.
+fn parse_option_types(meta: &Meta) -> Vec<(Path, Option<Type>)> {
+    parse_type_list(meta)
+        .into_iter()
+        .map(|entry| match entry {
+            Meta::Path(path) => (path, None),
+            Meta::NameValue(MetaNameValue { path, value, .. }) => {
+                let Some(inner) = expr_to_string(&value) else {
+                    abort!(value.span(), "Expected `option_types(Alias = \"Type\")`");
+                };
+                match syn::parse_str(&inner) {
+                    Ok(inner_ty) => (path, Some(inner_ty)),
+                    Err(e) => abort!(value.span(), "Invalid type found: {}", e),
+                }
+            }
+            entry => abort!(entry.span(), "Expected `option_types(Alias, ...)`"),
+        })
+        .collect()
+}
+
+/// A field, how the generated methods reach it and its parsed attributes.
+pub struct FieldModel<'a> {
+    pub field: &'a Field,
+    /// The position of the field, naming the fields of tuple structs.
+    pub index: usize,
+    pub access: Access<'a>,
+    pub config: ItemConfig,
+}
+
+impl<'a> FieldModel<'a> {
+    pub fn new(field: &'a Field, index: usize, access: Access<'a>) -> FieldModel<'a> {
+        FieldModel {
+            field,
+            index,
+            access,
+            config: ItemConfig::parse(&field.attrs),
+        }
+    }
+
+    /// The attribute in effect for the mode of `params`, from the field or else from the struct,
+    /// or `None` when the field gets no method.
+    pub fn attr<'b>(&'b self, params: &'b GenParams) -> Option<&'b ModeAttr> {
+        match self
+            .config
+            .decision(params.mode)
+            .or_else(|| params.config.decision(params.mode))
+        {
+            Some(Decision::Mode(attr)) => Some(attr.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// The attribute given on the field itself for the mode.
+    pub fn own_attr(&self, mode: GenMode) -> Option<&ModeAttr> {
+        self.config.mode(mode)
+    }
+
+    /// Whether the field gets a method of another mode of the same struct.
+    pub fn requests(&self, params: &GenParams, mode: GenMode) -> bool {
+        matches!(
+            self.config
+                .decision(mode)
+                .or_else(|| params.config.decision(mode)),
+            Some(Decision::Mode(_))
+        )
+    }
+
+    /// The span of the field name, or of the type for tuple structs, to report errors at.
+    pub fn name_span(&self) -> Span {
+        match &self.field.ident {
+            Some(ident) => ident.span(),
+            None => self.field.ty.span(),
+        }
+    }
+
+    /// The name of the field without `r#`, or its index for tuple structs.
+    pub fn base_name(&self) -> String {
+        match &self.field.ident {
+            Some(ident) => ident.unraw().to_string(),
+            None => self.index.to_string(),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use proc_macro_error2::abort;
+use syn::{ext::IdentExt, spanned::Spanned, Expr, Field, Meta, MetaNameValue, Path, Visibility};
+
+use crate::generate::{expr_to_string, Access, GenParams};
+use crate::model::{FieldModel, ModeAttr};
+
+/// The name of the error type returned by the validated setters, e.g. `FooSetError`.
+pub fn error_name(params: &GenParams) -> Ident {
+    Ident::new(
+        &format!("{}SetError", params.struct_name.unraw()),
+        Span::call_site(),
+    )
+}
+
+/// The error variant naming the field, e.g. `Port` for `port` or `Field0` for a tuple field.
+fn error_variant(field: &Field, index: usize) -> Ident {
+    let name = match &field.ident {
+        Some(ident) => ident
+            .unraw()
+            .to_string()
+            .split('_')
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let mut chars = part.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_uppercase().chain(chars).collect::<String>()
+                })
+            })
+            .collect(),
+        None => format!("Field{}", index),
+    };
+    Ident::new(&name, Span::call_site())
+}
+
+// Helper function to parse `validate = "..."` into the wrapped type
+pub fn parse_option_str<T: syn::parse::Parse>(option: &Meta) -> T {
+    let Meta::NameValue(MetaNameValue { value, path, .. }) = option else {
+        abort!(
+            option.span(),
+            "Expected `{} = \"...\"`",
+            path_name(option.path())
+        );
+    };
+    let Some(s) = expr_to_string(value) else {
+        abort!(value.span(), "Expected `{} = \"...\"`", path_name(path));
+    };
+    match syn::parse_str(&s) {
+        Ok(parsed) => parsed,
+        Err(e) => abort!(value.span(), "Invalid `{}` found: {}", path_name(path), e),
+    }
+}
+
+/// A check of a validated setter, e.g. `range = "1..=65535"` in `set(range = "1..=65535")`.
+#[derive(Clone)]
+pub enum Check {
+    /// `range = "..."`, with the range and its text.
+    Range(TokenStream2, String),
+    /// `non_empty`.
+    NonEmpty,
+    /// `validate = "..."`, with the path of the function.
+    Validate(Path),
+}
+
+/// Parses `validate = "..."`, `range = "..."` or `non_empty`.
+pub fn parse_check(option: &Meta) -> Check {
+    if option.path().is_ident("range") {
+        let (range, text) = parse_range(option);
+        Check::Range(range, text)
+    } else if option.path().is_ident("validate") {
+        Check::Validate(parse_option_str(option))
+    } else {
+        if !matches!(option, Meta::Path(_)) {
+            abort!(option.span(), "Expected `non_empty` without a value");
+        }
+        Check::NonEmpty
+    }
+}
+
+// Helper function to parse `range = "1..=65535"`, returning the range and its text
+fn parse_range(option: &Meta) -> (TokenStream2, String) {
+    let Meta::NameValue(MetaNameValue { value, .. }) = option else {
+        abort!(option.span(), "Expected `range = \"...\"`");
+    };
+    let Some(text) = expr_to_string(value) else {
+        abort!(value.span(), "Expected `range = \"...\"`");
+    };
+
+    // 范围表达式需要 syn 的 full 特性，这里手动拆分上下界
+    let (start, end, op) = if let Some((start, end)) = text.split_once("..=") {
+        (start, end, quote! { ..= })
+    } else if let Some((start, end)) = text.split_once("..") {
+        (start, end, quote! { .. })
+    } else {
+        abort!(
+            value.span(),
+            "Invalid `range` found: expected `start..end` or `start..=end`"
+        );
+    };
+    let bound = |s: &str| -> Option<Expr> {
+        let s = s.trim();
+        if s.is_empty() {
+            return None;
+        }
+        match syn::parse_str(s) {
+            Ok(expr) => Some(expr),
+            Err(e) => abort!(value.span(), "Invalid `range` found: {}", e),
+        }
+    };
+    let (start, end) = (bound(start), bound(end));
+
+    (quote! { #start #op #end }, text)
+}
+
+fn path_name(path: &Path) -> String {
+    path.get_ident().map_or_else(String::new, Ident::to_string)
+}
+
+/// Generates the checks of `set(validate = "...", range = "...", non_empty)` for the setter
+/// parameter, returning early with the field's error variant.
+///
+/// No checks means the field gets a plain setter.
+pub fn checks(
+    field: &FieldModel,
+    attr: &ModeAttr,
+    param: &Ident,
+    params: &GenParams,
+) -> Vec<TokenStream2> {
+    let error_name = error_name(params);
+    let variant = error_variant(field.field, field.index);
+
+    attr.checks
+        .iter()
+        .map(|check| match check {
+            Check::Range(range, text) => {
+                let reason = format!("must be in range {}", text);
+                quote! {
+                    if !(#range).contains(&#param) {
+                        return Err(#error_name::#variant(#reason.to_string()));
+                    }
+                }
+            }
+            Check::NonEmpty => quote! {
+                if #param.is_empty() {
+                    return Err(#error_name::#variant("must not be empty".to_string()));
+                }
+            },
+            Check::Validate(validate) => quote! {
+                if let Err(e) = #validate(&#param) {
+                    return Err(#error_name::#variant(e.to_string()));
+                }
+            },
+        })
+        .collect()
+}
+
+/// Generates the `FooSetError` enum with a variant per validated field, or nothing when no field
+/// is validated.
+pub fn implement_error(
+    vis: &Visibility,
+    fields: &[FieldModel],
+    params: &GenParams,
+) -> TokenStream2 {
+    let validated: Vec<(Ident, String)> = fields
+        .iter()
+        .filter(|field| {
+            // 只存在于部分枚举变体中的字段不会生成 setter
+            if let Access::Enum {
+                exhaustive: false, ..
+            } = field.access
+            {
+                return false;
+            }
+            field
+                .attr(params)
+                .is_some_and(|attr| !attr.checks.is_empty())
+        })
+        .map(|field| (error_variant(field.field, field.index), field.base_name()))
+        .collect();
+
+    if validated.is_empty() {
+        return quote! {};
+    }
+
+    let error_name = error_name(params);
+    let doc = format!(
+        "Error returned by the validated setters of `{}`.",
+        params.struct_name.unraw()
+    );
+    let variants = validated.iter().map(|(variant, name)| {
+        let doc = format!("The value of `{}` was rejected, with the reason.", name);
+        quote! {
+            #[doc = #doc]
+            #variant(String)
+        }
+    });
+    let (variant_names, field_names): (Vec<_>, Vec<_>) = validated.iter().cloned().unzip();
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis enum #error_name {
+            #(#variants,)*
+        }
+
+        impl #error_name {
+            /// The name of the rejected field.
+            pub fn field(&self) -> &'static str {
+                match self {
+                    #(Self::#variant_names(_) => #field_names,)*
+                }
+            }
+
+            /// Why the value was rejected.
+            pub fn reason(&self) -> &str {
+                match self {
+                    #(Self::#variant_names(reason))|* => reason,
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for #error_name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::write!(f, "invalid value for `{}`: {}", self.field(), self.reason())
+            }
+        }
+
+        impl ::std::error::Error for #error_name {}
+    }
+}
@@ -0,0 +1,142 @@
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use syn::{ext::IdentExt, Fields, Variant};
+
+use crate::generate::GenParams;
+use crate::model::{Decision, ItemConfig};
+
+/// Generates `is_`, `as_`, `as_*_mut` and `into_` helpers for a single enum variant.
+///
+/// `exhaustive` is set when the enum has no other variant, so no fallback arm is generated.
+pub fn implement(variant: &Variant, exhaustive: bool, params: &GenParams) -> TokenStream2 {
+    let config = ItemConfig::parse(&variant.attrs);
+    let attr = match config
+        .decision(params.mode)
+        .or_else(|| params.config.decision(params.mode))
+    {
+        Some(Decision::Mode(attr)) => attr.as_ref(),
+        // Generate nothing for skipped variant
+        Some(Decision::Skip) | None => return quote! {},
+    };
+
+    // 与字段的方法一样，默认为 pub
+    let visibility = attr.visibility();
+
+    let variant_name = &variant.ident;
+    let snake_name = to_snake_case(&variant_name.unraw().to_string());
+    let is_name = Ident::new(&format!("is_{}", snake_name), Span::call_site());
+    let doc = variant
+        .attrs
+        .iter()
+        .filter(|v| v.meta.path().is_ident("doc"));
+
+    let is_fn = quote! {
+        #(#doc)*
+        #[inline(always)]
+        #visibility fn #is_name(&self) -> bool {
+            matches!(self, Self::#variant_name { .. })
+        }
+    };
+
+    if variant.fields.is_empty() {
+        return is_fn;
+    }
+
+    let bindings: Vec<Ident> = (0..variant.fields.len())
+        .map(|i| Ident::new(&format!("__{}", i), Span::call_site()))
+        .collect();
+    let pattern = match &variant.fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|f| &f.ident);
+            quote! { Self::#variant_name { #(#names: #bindings),* } }
+        }
+        _ => quote! { Self::#variant_name(#(#bindings),*) },
+    };
+
+    let types: Vec<_> = variant.fields.iter().map(|f| &f.ty).collect();
+    let (ref_ty, mut_ty, owned_ty, value) = if types.len() == 1 {
+        let ty = types[0];
+        let binding = &bindings[0];
+        (
+            quote! { &#ty },
+            quote! { &mut #ty },
+            quote! { #ty },
+            quote! { #binding },
+        )
+    } else {
+        (
+            quote! { (#(&#types),*) },
+            quote! { (#(&mut #types),*) },
+            quote! { (#(#types),*) },
+            quote! { (#(#bindings),*) },
+        )
+    };
+
+    let (fallback, into_fallback) = if exhaustive {
+        (quote! {}, quote! {})
+    } else {
+        (quote! { _ => None, }, quote! { other => Err(other), })
+    };
+
+    let as_name = Ident::new(&format!("as_{}", snake_name), Span::call_site());
+    let as_mut_name = Ident::new(&format!("as_{}_mut", snake_name), Span::call_site());
+    let into_name = Ident::new(&format!("into_{}", snake_name), Span::call_site());
+    let doc = variant
+        .attrs
+        .iter()
+        .filter(|v| v.meta.path().is_ident("doc"));
+    let doc_mut = doc.clone();
+    let doc_into = doc.clone();
+
+    quote! {
+        #is_fn
+
+        #(#doc)*
+        #[inline(always)]
+        #visibility fn #as_name(&self) -> Option<#ref_ty> {
+            match self {
+                #pattern => Some(#value),
+                #fallback
+            }
+        }
+
+        #(#doc_mut)*
+        #[inline(always)]
+        #visibility fn #as_mut_name(&mut self) -> Option<#mut_ty> {
+            match self {
+                #pattern => Some(#value),
+                #fallback
+            }
+        }
+
+        #(#doc_into)*
+        #[inline(always)]
+        #visibility fn #into_name(self) -> Result<#owned_ty, Self> {
+            match self {
+                #pattern => Ok(#value),
+                #into_fallback
+            }
+        }
+    }
+}
+
+// Helper function to convert a variant name to snake case, e.g. `HttpRequest` to `http_request`
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+
+    snake
+}
@@ -0,0 +1,67 @@
+#[macro_use]
+extern crate zyc_getset;
+
+#[derive(Getters, Setters, Default)]
+#[getset(get)]
+pub struct Split {
+    #[getset(get)]
+    #[getset(set)]
+    name: String,
+    #[getset(set)]
+    #[get = "pub"]
+    id: u64,
+    #[getset(get, set)]
+    #[getset(skip)]
+    hidden: u8,
+    #[getset(set)]
+    setter_only: u8,
+    inherited: u8,
+}
+
+impl Split {
+    fn hidden(&self) -> u8 {
+        self.hidden
+    }
+
+    fn setter_only(&self) -> u8 {
+        self.setter_only
+    }
+}
+
+#[test]
+fn test_split_attributes() {
+    let mut val = Split::default();
+    val.set_name("foo".to_string()).set_id(1).set_setter_only(2);
+    assert_eq!("foo", val.name());
+    assert_eq!(1, val.id());
+    assert_eq!(0, val.hidden());
+    assert_eq!(2, val.setter_only());
+    assert_eq!(0, val.inherited());
+}
+
+// 字段上的注解整体替换 struct 上同一 mode 的注解，包括可见性、选项和校验
+#[derive(Getters, Setters, Default)]
+#[getset(get = "pub with_prefix", set(into, non_empty))]
+pub struct Replaced {
+    validated: String,
+    #[getset(set(into))]
+    unchecked: String,
+    #[getset(get, set)]
+    plain: String,
+}
+
+#[test]
+fn test_field_attribute_replaces_struct_attribute() {
+    let mut val = Replaced::default();
+    assert!(val.try_set_validated("").is_err());
+    val.try_set_validated("foo").unwrap();
+    assert_eq!("foo", val.get_validated());
+
+    // 没有继承 `non_empty`
+    val.set_unchecked("");
+    assert_eq!("", val.unchecked);
+
+    // 没有继承 `into` 和 `with_prefix`，参数类型需要是 `String`
+    val.set_plain(Default::default());
+    assert_eq!("", val.plain());
+}
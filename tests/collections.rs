@@ -0,0 +1,228 @@
+#[macro_use]
+extern crate zyc_getset;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
+
+#[derive(CollectionHelpers, Getters, Default)]
+#[getset(get, collection)]
+pub struct Plain {
+    items: Vec<u32>,
+    queue: VecDeque<u32>,
+    tags: HashSet<String>,
+    ordered_tags: BTreeSet<String>,
+    entries: HashMap<String, u32>,
+    #[getset(get, collection(singular = "score"))]
+    scoreboard: BTreeMap<String, u32>,
+    name: String,
+    count: u32,
+}
+
+#[derive(CollectionHelpers, Default)]
+pub struct Partial {
+    #[getset(collection = "pub(crate)")]
+    categories: Vec<String>,
+    #[getset(collection(singular = "child"))]
+    children: Vec<u32>,
+    untouched: Vec<u32>,
+}
+
+#[derive(CollectionHelpers)]
+#[getset(collection)]
+pub enum Batch {
+    Open { items: Vec<u32> },
+    Closed { items: Vec<u32>, note: String },
+}
+
+#[test]
+fn test_sequences() {
+    let mut val = Plain::default();
+    val.push_item(1)
+        .extend_items(vec![2, 3, 4])
+        .retain_items(|item| item % 2 == 0);
+    assert_eq!(val.items(), [2, 4]);
+    val.clear_items();
+    assert!(val.items().is_empty());
+
+    val.push_queue(1).extend_queue(vec![2]);
+    assert_eq!(val.queue(), &VecDeque::from(vec![1, 2]));
+}
+
+#[test]
+fn test_sequence_reads() {
+    let mut val = Plain::default();
+    assert!(val.items_is_empty());
+    val.extend_items(vec![1, 2, 3]).push_queue(4);
+    assert_eq!(Some(&2), val.items_at(1));
+    assert_eq!(None, val.items_at(3));
+    assert_eq!(3, val.items_len());
+    assert_eq!(6, val.items_iter().sum::<u32>());
+    assert_eq!(Some(&4), val.queue_at(0));
+    assert!(!val.queue_is_empty());
+}
+
+#[test]
+fn test_sets() {
+    let mut val = Plain::default();
+    assert!(val.insert_tag("foo".to_string()));
+    assert!(!val.insert_tag("foo".to_string()));
+    assert!(val.tags_contains("foo"));
+    assert!(val.remove_tag("foo"));
+    assert!(!val.tags_contains("foo"));
+    assert!(val.tags().is_empty());
+
+    val.extend_ordered_tags(vec!["b".to_string(), "a".to_string()])
+        .retain_ordered_tags(|tag| tag != "b");
+    assert_eq!(val.ordered_tags_iter().collect::<Vec<_>>(), ["a"]);
+    assert!(val.ordered_tags_contains("a"));
+    assert_eq!(1, val.ordered_tags_len());
+}
+
+#[test]
+fn test_maps() {
+    let mut val = Plain::default();
+    assert_eq!(None, val.insert_entry("foo".to_string(), 1));
+    assert_eq!(Some(1), val.insert_entry("foo".to_string(), 2));
+    assert_eq!(Some(&2), val.entries_get("foo"));
+    assert!(val.entries_contains("foo"));
+    assert_eq!(None, val.entries_get("bar"));
+    assert_eq!(Some(2), val.remove_entry("foo"));
+    assert!(val.entries_is_empty());
+
+    val.extend_scoreboard(vec![("a".to_string(), 1), ("b".to_string(), 2)])
+        .retain_scoreboard(|_, score| {
+            *score += 1;
+            *score > 2
+        });
+    assert_eq!(
+        val.scoreboard_iter().collect::<Vec<_>>(),
+        [(&"b".to_string(), &3)]
+    );
+    assert_eq!(Some(&3), val.scoreboard_get("b"));
+    assert_eq!(Some(3), val.remove_score("b"));
+    assert!(val.scoreboard().is_empty());
+}
+
+#[test]
+fn test_string() {
+    let mut val = Plain::default();
+    val.push_name("foo")
+        .push_name("-bar")
+        .retain_name(|c| c != '-');
+    assert_eq!(val.name(), "foobar");
+    assert_eq!(6, val.name_len());
+    val.clear_name();
+    assert_eq!(val.name(), "");
+}
+
+#[test]
+fn test_singular() {
+    let mut val = Partial::default();
+    val.push_category("foo".to_string()).push_child(1);
+    assert_eq!(val.categories, ["foo"]);
+    assert_eq!(val.children, [1]);
+    assert!(val.untouched.is_empty());
+}
+
+#[test]
+fn test_enum() {
+    let mut val = Batch::Closed {
+        items: vec![1],
+        note: String::new(),
+    };
+    val.push_item(2);
+    match val {
+        Batch::Closed { items, .. } => assert_eq!(items, [1, 2]),
+        Batch::Open { .. } => unreachable!(),
+    }
+
+    let mut val = Batch::Open { items: Vec::new() };
+    val.extend_items(vec![1, 2]);
+    assert_eq!(Some(&2), val.items_at(1));
+    assert_eq!(2, val.items_len());
+    val.clear_items();
+    assert!(val.items_is_empty());
+}
+
+#[derive(CollectionHelpers, Default)]
+#[getset(collection)]
+pub struct Generic<K: std::hash::Hash + Eq, T> {
+    items: Vec<T>,
+    lookup: HashMap<K, T>,
+}
+
+#[test]
+fn test_generic() {
+    let mut val = Generic::<String, u32>::default();
+    val.push_item(1).insert_lookup("a".to_string(), 2);
+    assert_eq!(vec![&1], val.items_iter().collect::<Vec<_>>());
+    assert_eq!(Some(&2), val.lookup_get("a"));
+}
+
+#[derive(CollectionHelpers, Default)]
+#[getset(collection)]
+pub struct Linked {
+    items: LinkedList<u32>,
+}
+
+#[test]
+fn test_linked_list() {
+    let mut val = Linked::default();
+    val.push_item(1).extend_items(vec![2, 3]);
+    assert_eq!(vec![&1, &2, &3], val.items_iter().collect::<Vec<_>>());
+    assert_eq!(3, val.items_len());
+    val.clear_items();
+    assert!(val.items_is_empty());
+}
+
+#[derive(CollectionHelpers, Default)]
+#[getset(collection)]
+pub struct Pair(Vec<u32>, HashMap<String, u32>);
+
+#[test]
+fn test_tuple_struct() {
+    let mut val = Pair::default();
+    val.push_0(1).extend_0(vec![2, 3]);
+    assert_eq!(Some(&2), val._0_at(1));
+    assert_eq!(vec![&1, &2, &3], val._0_iter().collect::<Vec<_>>());
+    assert_eq!(3, val._0_len());
+    val.insert_1("foo".to_string(), 1);
+    assert_eq!(Some(&1), val._1_get("foo"));
+    assert!(val._1_contains("foo"));
+    assert!(!val._1_is_empty());
+}
+
+#[derive(CollectionHelpers, Default)]
+pub struct ReadOnly {
+    #[getset(collection(read))]
+    tags: Vec<String>,
+    #[getset(collection(write))]
+    log: Vec<String>,
+    #[getset(collection(read, write))]
+    scores: HashMap<String, u32>,
+}
+
+// 没有生成 `push_tag` 和 `log_len`，可以自己实现同名方法
+impl ReadOnly {
+    pub fn push_tag(&mut self, tag: &str) {
+        self.tags.push(tag.to_lowercase());
+    }
+
+    pub fn log_len(&self) -> usize {
+        self.log.len()
+    }
+}
+
+#[test]
+fn test_read_and_write_helpers() {
+    let mut val = ReadOnly::default();
+    val.push_tag("Foo");
+    assert_eq!(Some("foo"), val.tags_at(0).map(String::as_str));
+    assert_eq!(1, val.tags_len());
+    assert!(!val.tags_is_empty());
+
+    val.push_log("started".to_string()).clear_log();
+    assert_eq!(0, val.log_len());
+
+    val.insert_score("foo".to_string(), 1);
+    assert_eq!(Some(&1), val.scores_get("foo"));
+}
@@ -0,0 +1,66 @@
+#[macro_use]
+extern crate zyc_getset;
+
+#[derive(Getters, CopyGetters, CloneGetters, Default)]
+pub struct Plain {
+    #[getset(get, get_clone)]
+    name: String,
+    #[getset(get, get_copy)]
+    id: u64,
+    #[getset(get_copy, get_clone)]
+    count: u32,
+    #[getset(get, get_copy, get_clone)]
+    all: u8,
+    #[getset(get, get_clone(name = "{}_owned"))]
+    label: String,
+    #[getset(get_clone)]
+    alone: String,
+}
+
+#[derive(Getters, CloneGetters)]
+#[getset(get, get_clone)]
+pub struct Global {
+    tags: Vec<String>,
+    #[getset(get)]
+    only_ref: String,
+}
+
+#[test]
+fn test_combined() {
+    let val = Plain {
+        name: "foo".to_string(),
+        ..Default::default()
+    };
+
+    let name: &str = val.name();
+    let name_cloned: String = val.name_cloned();
+    let id: u64 = val.id();
+    let id_copied: u64 = val.id_copied();
+    let count: u32 = val.count();
+    let count_cloned: u32 = val.count_cloned();
+    let all: u8 = val.all();
+    let all_copied: u8 = val.all_copied();
+    let all_cloned: u8 = val.all_cloned();
+    let label: &str = val.label();
+    let label_owned: String = val.label_owned();
+    let alone: String = val.alone();
+
+    assert_eq!("foo", name);
+    assert_eq!("foo", name_cloned);
+    assert_eq!((0, 0, 0, 0), (id, id_copied, count, count_cloned));
+    assert_eq!((0, 0, 0), (all, all_copied, all_cloned));
+    assert_eq!("", label);
+    assert!(label_owned.is_empty() && alone.is_empty());
+}
+
+#[test]
+fn test_global() {
+    let val = Global {
+        tags: vec!["foo".to_string()],
+        only_ref: "bar".to_string(),
+    };
+    let tags: &[String] = val.tags();
+    let tags_cloned: Vec<String> = val.tags_cloned();
+    assert_eq!(tags, &tags_cloned[..]);
+    assert_eq!("bar", val.only_ref());
+}
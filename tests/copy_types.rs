@@ -0,0 +1,245 @@
+#[macro_use]
+extern crate zyc_getset;
+
+use std::alloc;
+use std::cmp::Ordering;
+use std::num::NonZeroU32;
+use std::time::Duration;
+
+type MyInt = i32;
+
+pub mod ids {
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    pub struct Port(pub u16);
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    pub struct Uuid(pub u128);
+}
+
+use ids::{Port, Uuid};
+
+#[derive(Getters, Setters, Default)]
+#[getset(get, set, copy_types(MyInt, Port))]
+pub struct Plain {
+    id: MyInt,
+    port: Port,
+    backup: Option<ids::Port>,
+    ports: [Port; 2],
+    #[getset(copy_types(Uuid))]
+    uuid: Uuid,
+    other: Uuid,
+    name: String,
+}
+
+#[derive(Getters, Default)]
+pub struct FieldLevel {
+    #[getset(get, copy_types(MyInt))]
+    id: MyInt,
+    #[getset(copy_types(Uuid))]
+    uuid: Uuid,
+}
+
+#[derive(Getters)]
+#[getset(get, copy_types(Port))]
+pub enum Endpoint {
+    Local { port: Port },
+    Remote { port: Port, host: String },
+}
+
+#[test]
+fn test_copy_types() {
+    let mut val = Plain::default();
+    val.set_id(1).set_port(Port(80)).set_backup(Some(Port(81)));
+
+    let id: MyInt = val.id();
+    let port: Port = val.port();
+    let backup: Option<Port> = val.backup();
+    let ports: [Port; 2] = val.ports();
+    let uuid: Uuid = val.uuid();
+    let other: &Uuid = val.other();
+    let name: &str = val.name();
+
+    assert_eq!(1, id);
+    assert_eq!(Port(80), port);
+    assert_eq!(Some(Port(81)), backup);
+    assert_eq!([Port(0); 2], ports);
+    assert_eq!(Uuid(0), uuid);
+    assert_eq!(&Uuid(0), other);
+    assert_eq!("", name);
+}
+
+#[test]
+fn test_field_level() {
+    let val = FieldLevel::default();
+    let id: MyInt = val.id();
+    assert_eq!(0, id);
+    // `copy_types` alone doesn't skip the field nor generate anything
+    assert_eq!(Uuid(0), val.uuid);
+}
+
+#[test]
+fn test_enum() {
+    let val = Endpoint::Remote {
+        port: Port(80),
+        host: "localhost".to_string(),
+    };
+    let port: Port = val.port();
+    assert_eq!(Port(80), port);
+    assert_eq!(Some("localhost"), val.host());
+}
+
+#[derive(Getters, Default)]
+#[getset(get)]
+pub struct Bounded<T: Copy + Default, U, V: Clone>
+where
+    U: std::marker::Copy + Default,
+    V: Default,
+{
+    value: T,
+    other: U,
+    pair: (T, U),
+    many: [T; 2],
+    maybe: Option<U>,
+    cloned: V,
+}
+
+#[test]
+fn test_copy_bounded_params() {
+    let val = Bounded::<u8, u16, String>::default();
+    let value: u8 = val.value();
+    let other: u16 = val.other();
+    let pair: (u8, u16) = val.pair();
+    let many: [u8; 2] = val.many();
+    let maybe: Option<u16> = val.maybe();
+    let cloned: &String = val.cloned();
+    assert_eq!(
+        (0, 0, (0, 0), [0, 0], None),
+        (value, other, pair, many, maybe)
+    );
+    assert!(cloned.is_empty());
+}
+
+#[derive(Getters)]
+#[getset(get)]
+pub struct StdTypes<'a> {
+    timeout: std::time::Duration,
+    started: std::time::Instant,
+    modified: std::time::SystemTime,
+    order: std::cmp::Ordering,
+    ip: std::net::IpAddr,
+    addr: std::net::SocketAddr,
+    id: std::num::NonZeroU32,
+    type_id: std::any::TypeId,
+    marker: std::marker::PhantomData<String>,
+    unit: (),
+    label: &'a str,
+    maybe_label: Option<&'a String>,
+    ptr: *const u8,
+    wrapped: std::num::Wrapping<u32>,
+    reversed: core::cmp::Reverse<String>,
+}
+
+#[test]
+fn test_std_copy_types() {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::time::{Duration, Instant, SystemTime};
+
+    let owned = "owned".to_string();
+    let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let val = StdTypes {
+        timeout: Duration::from_secs(1),
+        started: Instant::now(),
+        modified: SystemTime::UNIX_EPOCH,
+        order: std::cmp::Ordering::Less,
+        ip,
+        addr: SocketAddr::new(ip, 80),
+        id: std::num::NonZeroU32::new(1).unwrap(),
+        type_id: std::any::TypeId::of::<u8>(),
+        marker: std::marker::PhantomData,
+        unit: (),
+        label: "label",
+        maybe_label: Some(&owned),
+        ptr: std::ptr::null(),
+        wrapped: std::num::Wrapping(1),
+        reversed: std::cmp::Reverse(String::new()),
+    };
+
+    let timeout: Duration = val.timeout();
+    let _: Instant = val.started();
+    let modified: SystemTime = val.modified();
+    let order: std::cmp::Ordering = val.order();
+    let ip: IpAddr = val.ip();
+    let addr: SocketAddr = val.addr();
+    let id: std::num::NonZeroU32 = val.id();
+    let type_id: std::any::TypeId = val.type_id();
+    let _: std::marker::PhantomData<String> = val.marker();
+    let unit: () = val.unit();
+    let label: &str = val.label();
+    let maybe_label: Option<&String> = val.maybe_label();
+    let ptr: *const u8 = val.ptr();
+    let wrapped: std::num::Wrapping<u32> = val.wrapped();
+    let reversed: &std::cmp::Reverse<String> = val.reversed();
+
+    assert_eq!(Duration::from_secs(1), timeout);
+    assert_eq!(SystemTime::UNIX_EPOCH, modified);
+    assert_eq!(std::cmp::Ordering::Less, order);
+    assert_eq!(80, addr.port());
+    assert!(ip.is_loopback());
+    assert_eq!(1, id.get());
+    assert_eq!(std::any::TypeId::of::<u8>(), type_id);
+    assert_eq!((), unit);
+    assert_eq!("label", label);
+    assert_eq!(Some(&owned), maybe_label);
+    assert!(ptr.is_null());
+    assert_eq!(1, wrapped.0);
+    assert!(reversed.0.is_empty());
+}
+
+#[derive(Getters)]
+#[getset(get)]
+pub struct BareStdTypes {
+    timeout: Duration,
+    order: Ordering,
+    id: NonZeroU32,
+}
+
+/// Shares its name with `std::alloc::Layout`, but isn't `Copy`.
+#[derive(Default)]
+pub struct Layout {
+    rows: Vec<u32>,
+}
+
+#[derive(Getters)]
+#[getset(get)]
+pub struct Screen {
+    layout: Layout,
+    std_layout: alloc::Layout,
+}
+
+#[test]
+fn test_bare_std_copy_types() {
+    let val = BareStdTypes {
+        timeout: Duration::from_secs(1),
+        order: Ordering::Less,
+        id: NonZeroU32::new(1).unwrap(),
+    };
+    let timeout: Duration = val.timeout();
+    let order: Ordering = val.order();
+    let id: NonZeroU32 = val.id();
+    assert_eq!(
+        (Duration::from_secs(1), Ordering::Less, 1),
+        (timeout, order, id.get())
+    );
+}
+
+#[test]
+fn test_user_type_named_like_std() {
+    let val = Screen {
+        layout: Layout::default(),
+        std_layout: alloc::Layout::new::<u32>(),
+    };
+    let layout: &Layout = val.layout();
+    let std_layout: alloc::Layout = val.std_layout();
+    assert!(layout.rows.is_empty());
+    assert_eq!(4, std_layout.size());
+}
@@ -0,0 +1,110 @@
+#[macro_use]
+extern crate zyc_getset;
+
+use std::ffi::{CStr, CString, OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+#[derive(Getters, Default)]
+#[getset(get)]
+pub struct Owned {
+    name: String,
+    items: Vec<u32>,
+    path: PathBuf,
+    os: OsString,
+    c: CString,
+    boxed: Box<u64>,
+    alias: Option<String>,
+    maybe_items: Option<Vec<u32>>,
+    maybe_boxed: Option<Box<u32>>,
+    #[getset(get(no_deref))]
+    raw_name: String,
+}
+
+#[test]
+fn test_borrowed_forms() {
+    let val = Owned {
+        name: "foo".to_string(),
+        items: vec![1, 2],
+        path: PathBuf::from("/tmp"),
+        os: OsString::from("os"),
+        c: CString::new("c").unwrap(),
+        boxed: Box::new(5),
+        alias: Some("bar".to_string()),
+        maybe_items: Some(vec![3]),
+        maybe_boxed: Some(Box::new(4)),
+        raw_name: "raw".to_string(),
+    };
+
+    let name: &str = val.name();
+    let items: &[u32] = val.items();
+    let path: &Path = val.path();
+    let os: &OsStr = val.os();
+    let c: &CStr = val.c();
+    let boxed: &u64 = val.boxed();
+    let alias: Option<&str> = val.alias();
+    let maybe_items: Option<&[u32]> = val.maybe_items();
+    let maybe_boxed: Option<&u32> = val.maybe_boxed();
+    let raw_name: &String = val.raw_name();
+
+    assert_eq!("foo", name);
+    assert_eq!(&[1, 2], items);
+    assert_eq!(Path::new("/tmp"), path);
+    assert_eq!("os", os);
+    assert_eq!(CString::new("c").unwrap().as_c_str(), c);
+    assert_eq!(&5, boxed);
+    assert_eq!(Some("bar"), alias);
+    assert_eq!(Some(&[3][..]), maybe_items);
+    assert_eq!(Some(&4), maybe_boxed);
+    assert_eq!("raw", raw_name);
+}
+
+#[derive(Getters, Default)]
+#[getset(get(no_deref))]
+pub struct Raw {
+    name: String,
+    alias: Option<String>,
+}
+
+#[test]
+fn test_no_deref_on_struct() {
+    let val = Raw::default();
+    let name: &String = val.name();
+    let alias: Option<&String> = val.alias();
+    assert!(name.is_empty());
+    assert!(alias.is_none());
+}
+
+pub trait Handler {
+    fn handle(&self) -> u32;
+}
+
+pub struct Answer;
+
+impl Handler for Answer {
+    fn handle(&self) -> u32 {
+        42
+    }
+}
+
+#[derive(Getters)]
+#[getset(get)]
+pub struct Dispatcher {
+    handler: Box<dyn Handler + Send>,
+    shared: Box<dyn Handler + Send + Sync>,
+    fallback: Option<Box<dyn Handler + Sync>>,
+}
+
+#[test]
+fn test_multi_bound_dyn() {
+    let val = Dispatcher {
+        handler: Box::new(Answer),
+        shared: Box::new(Answer),
+        fallback: Some(Box::new(Answer)),
+    };
+    let handler: &(dyn Handler + Send) = val.handler();
+    let shared: &(dyn Handler + Send + Sync) = val.shared();
+    let fallback: Option<&(dyn Handler + Sync)> = val.fallback();
+    assert_eq!(42, handler.handle());
+    assert_eq!(42, shared.handle());
+    assert_eq!(Some(42), fallback.map(Handler::handle));
+}
@@ -0,0 +1,151 @@
+#[macro_use]
+extern crate zyc_getset;
+
+#[derive(Getters, MutGetters, Setters, CloneGetters)]
+#[getset(get, get_mut, set)]
+pub enum Event {
+    Created {
+        /// The id of the event.
+        id: u64,
+        name: String,
+        parent: Option<String>,
+        retries: Option<u8>,
+    },
+    Deleted {
+        id: u64,
+        name: String,
+        reason: String,
+    },
+    Moved {
+        id: u64,
+        #[getset(get_clone)]
+        name: String,
+        parent: Option<String>,
+        retries: Option<u8>,
+        #[getset(skip)]
+        target: String,
+    },
+    Unit,
+    Tuple(u64),
+}
+
+fn created() -> Event {
+    Event::Created {
+        id: 1,
+        name: "foo".to_string(),
+        parent: Some("bar".to_string()),
+        retries: Some(3),
+    }
+}
+
+fn deleted() -> Event {
+    Event::Deleted {
+        id: 2,
+        name: "baz".to_string(),
+        reason: "gone".to_string(),
+    }
+}
+
+#[test]
+fn test_partial_fields() {
+    let val = created();
+    assert_eq!(Some(1), val.id());
+    assert_eq!(Some("foo".to_string()), val.name());
+    assert_eq!(Some("bar"), val.parent());
+    assert_eq!(Some(3), val.retries());
+    assert_eq!(None, val.reason());
+    assert_eq!(None, Event::Unit.id());
+
+    let val = deleted();
+    assert_eq!(None, val.parent());
+    assert_eq!(None, val.retries());
+    assert_eq!(Some("gone"), val.reason());
+}
+
+#[test]
+fn test_partial_mut_fields() {
+    let mut val = created();
+    *val.id_mut().unwrap() += 1;
+    assert_eq!(Some(2), val.id());
+    assert!(Event::Unit.id_mut().is_none());
+
+    // Option fields are flattened to `Option<&mut T>`
+    val.parent_mut().unwrap().push('!');
+    assert_eq!(Some("bar!"), val.parent());
+    assert!(deleted().retries_mut().is_none());
+}
+
+#[derive(Getters, MutGetters, Setters)]
+#[getset(get, get_mut, set)]
+pub enum Message {
+    Ping {
+        id: u64,
+        payload: Vec<u8>,
+    },
+    Pong {
+        id: u64,
+        payload: Vec<u8>,
+        latency: Option<u32>,
+    },
+}
+
+#[test]
+fn test_shared_fields() {
+    let mut val = Message::Ping {
+        id: 1,
+        payload: vec![1, 2],
+    };
+    assert_eq!(1, val.id());
+    assert_eq!(&vec![1, 2], val.payload());
+
+    val.set_id(2).set_payload(vec![3]);
+    val.payload_mut().push(4);
+    assert_eq!(2, val.id());
+    assert_eq!(&vec![3, 4], val.payload());
+    assert_eq!(None, val.latency());
+
+    let val = Message::Pong {
+        id: 3,
+        payload: vec![],
+        latency: Some(10),
+    };
+    assert_eq!(Some(10), val.latency());
+}
+
+#[derive(Getters)]
+pub enum Request {
+    Get {
+        #[getset(get_copy)]
+        id: u64,
+        /// The path of the request.
+        #[getset(get)]
+        path: String,
+    },
+    Post {
+        #[getset(get_copy)]
+        id: u64,
+        path: String,
+    },
+}
+
+#[derive(CopyGetters)]
+pub enum CopyRequest {
+    Get {
+        #[getset(get_copy)]
+        id: u64,
+    },
+    Post {
+        #[getset(get_copy)]
+        id: u64,
+    },
+}
+
+#[test]
+fn test_repeated_attributes() {
+    let val = Request::Post {
+        id: 1,
+        path: "/".to_string(),
+    };
+    assert_eq!("/", val.path());
+    assert_eq!(1, CopyRequest::Post { id: 1 }.id());
+}
@@ -0,0 +1,99 @@
+#![deny(deprecated)]
+
+#[macro_use]
+extern crate zyc_getset;
+
+#[derive(Getset, Default, Debug)]
+#[getset(get, set)]
+pub struct Plain {
+    id: u64,
+    name: String,
+    label: Option<String>,
+    #[getset(get_clone, get, get_mut, with)]
+    tags: Vec<String>,
+    #[getset(get_copy, set(range = "1..=10"))]
+    level: u8,
+    #[getset(skip)]
+    hidden: bool,
+}
+
+#[derive(Getset)]
+#[getset(get, get_mut, set)]
+pub enum Shape {
+    Circle { id: u64, radius: f64 },
+    Square { id: u64, side: f64 },
+}
+
+#[derive(Getset, Default)]
+pub struct Tuple(#[getset(get, set)] u32, #[getset(get_mut)] String);
+
+// 只写的字段通过 `allow(deprecated)` 关闭警告
+#[derive(Getset, Default)]
+pub struct WriteOnly {
+    #[getset(set)]
+    #[allow(deprecated)]
+    secret: String,
+}
+
+#[derive(Getset, Default)]
+#[allow(deprecated)]
+pub struct WriteOnlyStruct {
+    #[getset(set)]
+    secret: String,
+}
+
+#[test]
+fn test_all_modes() {
+    let mut val = Plain::default();
+    val.set_id(1)
+        .set_name("foo".to_string())
+        .set_label(Some("bar".to_string()));
+    val.tags_mut().push("baz".to_string());
+
+    let id: u64 = val.id();
+    let name: &str = val.name();
+    let label: Option<&str> = val.label();
+    let tags: &[String] = val.tags();
+    let tags_cloned: Vec<String> = val.tags_cloned();
+    assert_eq!((1, "foo", Some("bar")), (id, name, label));
+    assert_eq!(tags, &tags_cloned[..]);
+
+    let err = val.try_set_level(11).unwrap_err();
+    assert_eq!("level", err.field());
+    val.try_set_level(2).unwrap();
+    assert_eq!(2, val.level());
+
+    let val = val.with_tags(vec![]);
+    assert!(val.tags().is_empty());
+    assert!(!val.hidden);
+}
+
+#[test]
+fn test_enum() {
+    let mut val = Shape::Circle { id: 1, radius: 2.0 };
+    val.set_id(2);
+    *val.radius_mut().unwrap() += 1.0;
+    assert_eq!(2, val.id());
+    assert_eq!(Some(3.0), val.radius());
+    assert_eq!(None, val.side());
+}
+
+#[test]
+fn test_tuple() {
+    let mut val = Tuple::default();
+    val.set_0(1);
+    val._1_mut().push('a');
+    assert_eq!(1, val._0());
+    assert_eq!("a", val.1);
+}
+
+#[test]
+fn test_write_only() {
+    let mut val = WriteOnly::default();
+    val.set_secret("foo".to_string());
+    assert_eq!("foo", val.secret);
+
+    let mut val = WriteOnlyStruct::default();
+    val.set_secret("bar".to_string());
+    assert_eq!("bar", val.secret);
+}
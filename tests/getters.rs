@@ -5,7 +5,7 @@ use crate::submodule::other::{Generic, Plain, Where};
 
 // For testing `pub(super)`
 mod submodule {
-    // For testing `pub(super::other)`
+    // For testing `pub(in super::other)`
     pub mod other {
         #[derive(Getters)]
         #[get]
@@ -17,17 +17,17 @@ mod submodule {
             /// A doc comment.
             #[get = "pub"]
             public_accessible: usize,
-            // /// A doc comment.
-            // #[get = "pub(crate)"]
-            // crate_accessible: usize,
+            /// A doc comment.
+            #[get = "pub(crate)"]
+            crate_accessible: usize,
 
-            // /// A doc comment.
-            // #[get = "pub(super)"]
-            // super_accessible: usize,
+            /// A doc comment.
+            #[get = "pub(super)"]
+            super_accessible: usize,
 
-            // /// A doc comment.
-            // #[get = "pub(super::other)"]
-            // scope_accessible: usize,
+            /// A doc comment.
+            #[get = "pub(in super::other)"]
+            scope_accessible: usize,
 
             // Prefixed getter.
             #[get = "with_prefix"]
@@ -43,6 +43,9 @@ mod submodule {
                 Plain {
                     private_accessible: 17,
                     public_accessible: 18,
+                    crate_accessible: 21,
+                    super_accessible: 22,
+                    scope_accessible: 23,
                     private_prefixed: 19,
                     public_prefixed: 20,
                 }
@@ -59,17 +62,17 @@ mod submodule {
             /// A doc comment.
             #[get = "pub(crate)"]
             public_accessible: T,
-            // /// A doc comment.
-            // #[get = "pub(crate)"]
-            // crate_accessible: usize,
+            /// A doc comment.
+            #[get = "pub(crate)"]
+            crate_accessible: usize,
 
-            // /// A doc comment.
-            // #[get = "pub(super)"]
-            // super_accessible: usize,
+            /// A doc comment.
+            #[get = "pub(super)"]
+            super_accessible: usize,
 
-            // /// A doc comment.
-            // #[get = "pub(super::other)"]
-            // scope_accessible: usize,
+            /// A doc comment.
+            #[get = "pub(in super::other)"]
+            scope_accessible: usize,
         }
 
         #[derive(Getters, Default)]
@@ -85,17 +88,17 @@ mod submodule {
             /// A doc comment.
             #[get = "pub"]
             public_accessible: T,
-            // /// A doc comment.
-            // #[get = "pub(crate)"]
-            // crate_accessible: usize,
+            /// A doc comment.
+            #[get = "pub(crate)"]
+            crate_accessible: usize,
 
-            // /// A doc comment.
-            // #[get = "pub(super)"]
-            // super_accessible: usize,
+            /// A doc comment.
+            #[get = "pub(super)"]
+            super_accessible: usize,
 
-            // /// A doc comment.
-            // #[get = "pub(super::other)"]
-            // scope_accessible: usize,
+            /// A doc comment.
+            #[get = "pub(in super::other)"]
+            scope_accessible: usize,
         }
 
         #[test]
@@ -108,42 +111,68 @@ mod submodule {
         fn test_generic() {
             let val = Generic::<usize>::default();
             val.private_accessible();
+            assert_eq!(0, val.crate_accessible());
+            assert_eq!(0, val.super_accessible());
+            assert_eq!(0, val.scope_accessible());
         }
 
         #[test]
         fn test_where() {
             let val = Where::<usize>::default();
             val.private_accessible();
+            assert_eq!(0, val.crate_accessible());
+            assert_eq!(0, val.super_accessible());
+            assert_eq!(0, val.scope_accessible());
         }
 
         #[test]
         fn test_prefixed_plain() {
             let val = Plain::default();
-            assert_eq!(19, *val.get_private_prefixed());
+            assert_eq!(19, val.get_private_prefixed());
+        }
+
+        #[test]
+        fn test_restricted_plain() {
+            let val = Plain::default();
+            assert_eq!(21, val.crate_accessible());
+            assert_eq!(22, val.super_accessible());
+            assert_eq!(23, val.scope_accessible());
         }
     }
+
+    #[test]
+    fn test_super_plain() {
+        let val = other::Plain::default();
+        assert_eq!(22, val.super_accessible());
+    }
 }
 
 #[test]
 fn test_plain() {
     let val = Plain::default();
-    assert_eq!(18, *val.public_accessible());
+    assert_eq!(18, val.public_accessible());
 }
 
 #[test]
 fn test_generic() {
     let val = Generic::<usize>::default();
-    assert_eq!(usize::default(), *val.public_accessible());
+    assert_eq!(usize::default(), val.public_accessible());
 }
 
 #[test]
 fn test_where() {
     let val = Where::<usize>::default();
-    assert_eq!(usize::default(), *val.public_accessible());
+    assert_eq!(usize::default(), val.public_accessible());
 }
 
 #[test]
 fn test_prefixed_plain() {
     let val = Plain::default();
-    assert_eq!(20, *val.get_public_prefixed());
+    assert_eq!(20, val.get_public_prefixed());
+}
+
+#[test]
+fn test_crate_plain() {
+    let val = Plain::default();
+    assert_eq!(21, val.crate_accessible());
 }
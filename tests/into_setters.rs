@@ -0,0 +1,58 @@
+#[macro_use]
+extern crate zyc_getset;
+
+use std::path::PathBuf;
+
+#[derive(Setters, Getters, Default)]
+#[getset(get, set(into))]
+pub struct Plain {
+    name: String,
+    path: PathBuf,
+    parent: Option<String>,
+    count: u64,
+}
+
+#[derive(Setters, Getters, Default)]
+#[getset(get, set)]
+pub struct Mixed {
+    #[getset(get, set(into))]
+    name: String,
+    count: u64,
+}
+
+#[derive(Setters, MutGetters)]
+#[getset(set(into), get_mut)]
+pub enum Shape {
+    Circle { label: String },
+    Square { label: String },
+}
+
+#[test]
+fn test_into() {
+    let mut val = Plain::default();
+    val.set_name("foo")
+        .set_path("/tmp")
+        .set_parent("bar".to_string())
+        .set_count(3u8);
+    assert_eq!("foo", val.name());
+    assert_eq!(PathBuf::from("/tmp"), val.path());
+    assert_eq!(Some("bar"), val.parent());
+    assert_eq!(3, val.count());
+}
+
+#[test]
+fn test_into_on_field() {
+    let mut val = Mixed::default();
+    val.set_name("foo").set_count(3);
+    assert_eq!("foo", val.name());
+    assert_eq!(3, val.count());
+}
+
+#[test]
+fn test_into_on_enum() {
+    let mut val = Shape::Circle {
+        label: String::new(),
+    };
+    val.set_label("foo");
+    assert_eq!("foo", val.label_mut());
+}
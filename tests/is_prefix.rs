@@ -0,0 +1,67 @@
+#[macro_use]
+extern crate zyc_getset;
+
+#[derive(Getters, Setters, CloneGetters, Default)]
+#[getset(get(is_prefix), set)]
+pub struct Plain {
+    enabled: bool,
+    visible: Option<bool>,
+    is_active: bool,
+    has_children: bool,
+    r#static: bool,
+    name: String,
+    #[getset(get_clone(is_prefix))]
+    cloned: bool,
+}
+
+#[derive(Getters, Default)]
+pub struct FieldLevel {
+    #[getset(get(is_prefix))]
+    ready: bool,
+    #[getset(get)]
+    done: bool,
+}
+
+#[derive(Getters)]
+#[getset(get(is_prefix))]
+pub enum State {
+    Running { paused: bool },
+    Stopped { paused: bool, failed: bool },
+}
+
+#[test]
+fn test_is_prefix() {
+    let mut val = Plain::default();
+    val.set_enabled(true)
+        .set_visible(Some(true))
+        .set_is_active(true)
+        .set_has_children(true)
+        .set_static(true)
+        .set_name("foo".to_string());
+
+    assert!(val.is_enabled());
+    assert_eq!(Some(true), val.is_visible());
+    assert!(val.is_active());
+    assert!(val.has_children());
+    assert!(val.is_static());
+    assert_eq!("foo", val.name());
+    assert!(!val.is_cloned());
+}
+
+#[test]
+fn test_field_level() {
+    let val = FieldLevel::default();
+    assert!(!val.is_ready());
+    assert!(!val.done());
+}
+
+#[test]
+fn test_enum() {
+    let val = State::Stopped {
+        paused: true,
+        failed: false,
+    };
+    assert!(val.is_paused());
+    assert_eq!(Some(false), val.is_failed());
+    assert_eq!(None, State::Running { paused: false }.is_failed());
+}
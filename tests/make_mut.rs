@@ -0,0 +1,84 @@
+#[macro_use]
+extern crate zyc_getset;
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    port: u16,
+}
+
+#[derive(MutGetters, Getters, Clone, Default)]
+#[getset(get, get_mut(make_mut))]
+pub struct Snapshot {
+    config: Arc<Config>,
+    names: Rc<Vec<String>>,
+    count: u32,
+}
+
+#[derive(MutGetters, Default)]
+pub struct Mixed {
+    #[getset(get_mut(make_mut))]
+    config: Arc<Config>,
+    #[getset(get_mut)]
+    shared: Arc<Config>,
+}
+
+#[derive(MutGetters)]
+#[getset(get_mut(make_mut))]
+pub enum Source {
+    Local {
+        config: Rc<Config>,
+    },
+    Remote {
+        config: Rc<Config>,
+        url: Arc<String>,
+    },
+}
+
+#[test]
+fn test_make_mut() {
+    let mut val = Snapshot::default();
+    let snapshot = val.clone();
+
+    val.config_mut().port = 8080;
+    val.names_mut().push("foo".to_string());
+    *val.count_mut() += 1;
+
+    assert_eq!(8080, val.config().port);
+    assert_eq!(0, snapshot.config().port);
+    assert_eq!(&["foo".to_string()], &val.names()[..]);
+    assert!(snapshot.names().is_empty());
+    assert_eq!(1, val.count());
+}
+
+#[test]
+fn test_field_level() {
+    let mut val = Mixed::default();
+    val.config_mut().port = 1;
+    let shared: &mut Arc<Config> = val.shared_mut();
+    *shared = Arc::new(Config { port: 2 });
+    assert_eq!(Config { port: 1 }, *val.config);
+    assert_eq!(2, val.shared.port);
+}
+
+#[test]
+fn test_enum() {
+    let config = Rc::new(Config::default());
+    let mut val = Source::Remote {
+        config: config.clone(),
+        url: Arc::new("foo".to_string()),
+    };
+    val.config_mut().port = 80;
+    val.url_mut().unwrap().push_str("bar");
+
+    assert_eq!(0, config.port);
+    match val {
+        Source::Remote { config, url } => {
+            assert_eq!(80, config.port);
+            assert_eq!("foobar", *url);
+        }
+        Source::Local { .. } => unreachable!(),
+    }
+}
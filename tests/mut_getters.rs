@@ -17,17 +17,17 @@ mod submodule {
             /// A doc comment.
             #[get_mut = "pub"]
             public_accessible: usize,
-            // /// A doc comment.
-            // #[get_mut = "pub(crate)"]
-            // crate_accessible: usize,
+            /// A doc comment.
+            #[get_mut = "pub(crate)"]
+            crate_accessible: usize,
 
-            // /// A doc comment.
-            // #[get_mut = "pub(super)"]
-            // super_accessible: usize,
+            /// A doc comment.
+            #[get_mut = "pub(super)"]
+            super_accessible: usize,
 
-            // /// A doc comment.
-            // #[get_mut = "pub(in super::other)"]
-            // scope_accessible: usize,
+            /// A doc comment.
+            #[get_mut = "pub(in super::other)"]
+            scope_accessible: usize,
         }
 
         #[derive(MutGetters, Default)]
@@ -40,17 +40,17 @@ mod submodule {
             /// A doc comment.
             #[get_mut = "pub"]
             public_accessible: T,
-            // /// A doc comment.
-            // #[get_mut = "pub(crate)"]
-            // crate_accessible: usize,
+            /// A doc comment.
+            #[get_mut = "pub(crate)"]
+            crate_accessible: usize,
 
-            // /// A doc comment.
-            // #[get_mut = "pub(super)"]
-            // super_accessible: usize,
+            /// A doc comment.
+            #[get_mut = "pub(super)"]
+            super_accessible: usize,
 
-            // /// A doc comment.
-            // #[get_mut = "pub(in super::other)"]
-            // scope_accessible: usize,
+            /// A doc comment.
+            #[get_mut = "pub(in super::other)"]
+            scope_accessible: usize,
         }
 
         #[derive(MutGetters, Default)]
@@ -66,17 +66,17 @@ mod submodule {
             /// A doc comment.
             #[get_mut = "pub"]
             public_accessible: T,
-            // /// A doc comment.
-            // #[get_mut = "pub(crate)"]
-            // crate_accessible: usize,
+            /// A doc comment.
+            #[get_mut = "pub(crate)"]
+            crate_accessible: usize,
 
-            // /// A doc comment.
-            // #[get_mut = "pub(super)"]
-            // super_accessible: usize,
+            /// A doc comment.
+            #[get_mut = "pub(super)"]
+            super_accessible: usize,
 
-            // /// A doc comment.
-            // #[get_mut = "pub(in super::other)"]
-            // scope_accessible: usize,
+            /// A doc comment.
+            #[get_mut = "pub(in super::other)"]
+            scope_accessible: usize,
         }
 
         #[test]
@@ -89,13 +89,33 @@ mod submodule {
         fn test_generic() {
             let mut val = Generic::<usize>::default();
             (*val.private_accessible_mut()) += 1;
+            *val.crate_accessible_mut() += 1;
+            *val.super_accessible_mut() += 1;
+            *val.scope_accessible_mut() += 1;
         }
 
         #[test]
         fn test_where() {
             let mut val = Where::<usize>::default();
             (*val.private_accessible_mut()) += 1;
+            *val.crate_accessible_mut() += 1;
+            *val.super_accessible_mut() += 1;
+            *val.scope_accessible_mut() += 1;
         }
+
+        #[test]
+        fn test_restricted_plain() {
+            let mut val = Plain::default();
+            *val.crate_accessible_mut() += 1;
+            *val.super_accessible_mut() += 1;
+            *val.scope_accessible_mut() += 1;
+        }
+    }
+
+    #[test]
+    fn test_super_plain() {
+        let mut val = other::Plain::default();
+        *val.super_accessible_mut() += 1;
     }
 }
 
@@ -116,3 +136,9 @@ fn test_where() {
     let mut val = Where::<usize>::default();
     (*val.public_accessible_mut()) += 1;
 }
+
+#[test]
+fn test_crate_plain() {
+    let mut val = Plain::default();
+    *val.crate_accessible_mut() += 1;
+}
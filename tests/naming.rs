@@ -0,0 +1,71 @@
+#[macro_use]
+extern crate zyc_getset;
+
+#[derive(Getters, Setters, MutGetters, Default)]
+#[getset(get, set(name = "put_{}"), get_mut(name = "{}_ref_mut"))]
+pub struct Templates {
+    count: u32,
+    name: String,
+    #[getset(get, set(prefix = "assign_"), get_mut)]
+    label: String,
+    #[getset(get(name = "title"), set, get_mut(suffix = "_mut"))]
+    heading: String,
+    r#type: u8,
+}
+
+#[derive(Getters, Setters, WithSetters, Default)]
+#[getset(
+    get(prefix = "fetch_"),
+    set(prefix = "", suffix = "_is"),
+    with(prefix = "and_")
+)]
+pub struct Affixes {
+    count: u32,
+    r#type: u8,
+}
+
+#[derive(Getters, MutGetters, Default)]
+#[getset(get(name = "{}_value"), get_mut(name = "{}_value_mut"))]
+pub struct Tuple(u32, String);
+
+#[test]
+fn test_templates() {
+    let mut val = Templates::default();
+    val.put_count(1)
+        .put_name("foo".to_string())
+        .assign_label("bar".to_string())
+        .set_heading("baz".to_string())
+        .put_type(2);
+    val.count_ref_mut().clone_from(&2);
+    val.name_ref_mut().push('!');
+    // 字段上的 `get_mut` 替换了 struct 上的 `get_mut(name = "{}_ref_mut")`
+    val.label_mut().push('?');
+    val.heading_mut().push('.');
+    *val.type_ref_mut() += 1;
+
+    assert_eq!(2, val.count());
+    assert_eq!("foo!", val.name());
+    assert_eq!("bar?", val.label());
+    assert_eq!("baz.", val.title());
+    assert_eq!(3, val.r#type());
+}
+
+#[test]
+fn test_affixes() {
+    let mut val = Affixes::default();
+    val.count_is(1).type_is(2);
+    assert_eq!(1, val.fetch_count());
+    assert_eq!(2, val.fetch_type());
+
+    let val = val.and_count(3);
+    assert_eq!(3, val.fetch_count());
+}
+
+#[test]
+fn test_tuple_templates() {
+    let mut val = Tuple::default();
+    *val._0_value_mut() += 1;
+    val._1_value_mut().push_str("foo");
+    assert_eq!(1, val._0_value());
+    assert_eq!("foo", val._1_value());
+}
@@ -0,0 +1,118 @@
+#[macro_use]
+extern crate zyc_getset;
+
+#[derive(Getters, Setters, MutGetters, Default)]
+#[getset(get(has), set(some, clear, take, replace), get_mut(or_insert_with))]
+pub struct Plain {
+    name: Option<String>,
+    port: Option<u16>,
+    count: u32,
+}
+
+#[derive(Getters, Setters, Default)]
+#[getset(get, set)]
+pub struct Mixed {
+    #[getset(get, set(some, into, take))]
+    name: Option<String>,
+    parent: Option<String>,
+}
+
+#[derive(Getters, MutGetters, Default)]
+#[getset(get, get_mut)]
+pub struct Editable {
+    name: Option<String>,
+    #[getset(get, get_mut(raw))]
+    parent: Option<String>,
+}
+
+#[derive(Getters, Setters)]
+#[getset(get(has), set(some, clear, take, replace))]
+pub enum Shape {
+    Circle { label: Option<String> },
+    Square { label: Option<String> },
+}
+
+#[test]
+fn test_set_some() {
+    let mut val = Plain::default();
+    val.set_name("foo".to_string()).set_port(80).set_count(3);
+    assert_eq!(Some("foo"), val.name());
+    assert_eq!(Some(80), val.port());
+    assert_eq!(3, val.count());
+}
+
+#[test]
+fn test_clear_take_replace() {
+    let mut val = Plain::default();
+    assert!(!val.has_name());
+    assert_eq!(None, val.replace_name("foo".to_string()));
+    assert!(val.has_name());
+    assert_eq!(Some("foo".to_string()), val.replace_name("bar".to_string()));
+    assert_eq!(Some("bar".to_string()), val.take_name());
+    assert_eq!(None, val.take_name());
+
+    val.set_port(80).clear_port();
+    assert!(!val.has_port());
+}
+
+#[test]
+fn test_or_insert_with() {
+    let mut val = Plain::default();
+    val.name_or_insert_with(|| "foo".to_string()).push('!');
+    *val.port_or_insert_with(|| 1) += 1;
+    assert_eq!(Some("foo!"), val.name());
+    assert_eq!(Some(2), val.port());
+}
+
+#[test]
+fn test_get_mut() {
+    let mut val = Editable::default();
+    assert_eq!(None, val.name_mut());
+
+    val.name = Some("foo".to_string());
+    let name: Option<&mut String> = val.name_mut();
+    name.unwrap().push('!');
+    assert_eq!(Some("foo!"), val.name());
+
+    let parent: &mut Option<String> = val.parent_mut();
+    *parent = Some("bar".to_string());
+    assert_eq!(Some("bar"), val.parent());
+}
+
+#[test]
+fn test_field_level() {
+    let mut val = Mixed::default();
+    val.set_name("foo").set_parent(Some("bar".to_string()));
+    assert_eq!(Some("foo".to_string()), val.take_name());
+    assert_eq!(Some("bar"), val.parent());
+}
+
+#[test]
+fn test_enum() {
+    let mut val = Shape::Circle { label: None };
+    assert!(!val.has_label());
+    val.set_label("foo".to_string());
+    assert_eq!(Some("foo"), val.label());
+    assert_eq!(
+        Some("foo".to_string()),
+        val.replace_label("bar".to_string())
+    );
+    val.clear_label();
+    assert_eq!(None, val.take_label());
+}
+
+#[derive(Getters, Setters, MutGetters, Default)]
+#[getset(get(has), set(some, clear, take, replace), get_mut(or_insert_with))]
+pub struct Wrapper(Option<u32>);
+
+#[test]
+fn test_tuple_struct() {
+    let mut val = Wrapper::default();
+    assert!(!val.has_0());
+    *val._0_or_insert_with(|| 1) += 1;
+    assert_eq!(Some(2), val._0());
+    assert_eq!(Some(2), val.replace_0(3));
+    assert_eq!(Some(3), val.take_0());
+    val.set_0(4).clear_0();
+    assert_eq!(None, val._0());
+}
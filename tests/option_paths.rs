@@ -0,0 +1,82 @@
+#[macro_use]
+extern crate zyc_getset;
+
+type MaybeId = Option<u64>;
+type MaybeName = Option<String>;
+type Maybe<T> = Option<T>;
+
+pub mod custom {
+    /// A user type that happens to be named `Option`.
+    #[derive(Debug, Default, PartialEq)]
+    pub struct Option<T>(pub T);
+}
+
+#[derive(Getters, Setters, MutGetters, Default)]
+#[getset(get, set, get_mut, option_types(MaybeId = "u64", Maybe))]
+pub struct Plain {
+    std_path: std::option::Option<String>,
+    core_path: core::option::Option<u32>,
+    custom: custom::Option<String>,
+    id: MaybeId,
+    generic: Maybe<String>,
+    #[getset(get, set(some), option_types(MaybeName = "String"))]
+    name: MaybeName,
+    nested: Option<Option<String>>,
+    #[getset(get(flatten), set)]
+    flattened: Option<Option<String>>,
+    nested_copy: Option<Option<u8>>,
+    #[getset(get(flatten), set)]
+    flattened_copy: Option<Option<u8>>,
+}
+
+#[test]
+fn test_qualified_paths() {
+    let mut val = Plain::default();
+    val.set_std_path(Some("foo".to_string()))
+        .set_core_path(Some(1));
+    let std_path: Option<&str> = val.std_path();
+    let core_path: Option<u32> = val.core_path();
+    let custom: &custom::Option<String> = val.custom();
+    assert_eq!(Some("foo"), std_path);
+    assert_eq!(Some(1), core_path);
+    assert_eq!(&custom::Option(String::new()), custom);
+
+    val.std_path_mut().unwrap().push('!');
+    assert_eq!(Some("foo!"), val.std_path());
+}
+
+#[test]
+fn test_aliases() {
+    let mut val = Plain::default();
+    val.set_id(Some(1))
+        .set_generic(Some("bar".to_string()))
+        .set_name("baz".to_string());
+
+    let id: Option<u64> = val.id();
+    let generic: Option<&String> = val.generic();
+    let name: Option<&str> = val.name();
+    assert_eq!(Some(1), id);
+    assert_eq!(Some(&"bar".to_string()), generic);
+    assert_eq!(Some("baz"), name);
+
+    *val.id_mut().unwrap() += 1;
+    assert_eq!(Some(2), val.id());
+}
+
+#[test]
+fn test_nested() {
+    let mut val = Plain::default();
+    val.set_nested(Some(None))
+        .set_flattened(Some(Some("foo".to_string())))
+        .set_nested_copy(Some(Some(1)))
+        .set_flattened_copy(Some(None));
+
+    let nested: Option<Option<&str>> = val.nested();
+    let flattened: Option<&str> = val.flattened();
+    let nested_copy: Option<Option<u8>> = val.nested_copy();
+    let flattened_copy: Option<u8> = val.flattened_copy();
+    assert_eq!(Some(None), nested);
+    assert_eq!(Some("foo"), flattened);
+    assert_eq!(Some(Some(1)), nested_copy);
+    assert_eq!(None, flattened_copy);
+}
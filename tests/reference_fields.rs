@@ -0,0 +1,97 @@
+#[macro_use]
+extern crate zyc_getset;
+
+#[derive(Getters, Setters)]
+#[getset(get, set)]
+pub struct Token<'a> {
+    name: &'a str,
+    bytes: &'a [u8],
+    next: Option<&'a str>,
+    parent: Option<&'a Token<'a>>,
+}
+
+#[derive(Getters, MutGetters)]
+#[getset(get, get_mut)]
+pub struct Cursor<'a> {
+    buf: &'a mut Vec<u8>,
+    scratch: Option<&'a mut String>,
+    #[getset(get(no_deref), get_mut)]
+    raw: &'a mut String,
+}
+
+#[derive(Getters)]
+#[getset(get)]
+pub enum Node<'a> {
+    Leaf { text: &'a str },
+    Branch { text: &'a str, children: &'a [u8] },
+}
+
+// The returned references outlive the borrow of the struct
+fn name_of<'a>(token: &Token<'a>) -> &'a str {
+    token.name()
+}
+
+fn parts_of<'a>(token: Token<'a>) -> (&'a str, &'a [u8], Option<&'a str>) {
+    (token.name(), token.bytes(), token.next())
+}
+
+#[test]
+fn test_shared_references() {
+    let source = String::from("foo bar");
+    let bytes = [1, 2];
+    let parent = Token {
+        name: &source[..3],
+        bytes: &bytes,
+        next: None,
+        parent: None,
+    };
+    let mut token = Token {
+        name: &source[4..],
+        bytes: &bytes[1..],
+        next: Some(&source[..3]),
+        parent: Some(&parent),
+    };
+
+    assert_eq!("bar", name_of(&token));
+    assert_eq!("foo", token.parent().map(|parent| parent.name()).unwrap());
+    token.set_next(None);
+    assert_eq!(("bar", &[2][..], None), parts_of(token));
+}
+
+#[test]
+fn test_mutable_references() {
+    let mut buf = vec![1];
+    let mut scratch = String::new();
+    let mut raw = String::from("raw");
+    let mut cursor = Cursor {
+        buf: &mut buf,
+        scratch: Some(&mut scratch),
+        raw: &mut raw,
+    };
+
+    // `&'a mut Vec<u8>` 和 `Vec<u8>` 一样返回 `&[u8]`
+    let view: &[u8] = cursor.buf();
+    assert_eq!([1], view);
+    cursor.buf_mut().push(2);
+    cursor.scratch_mut().unwrap().push_str("foo");
+    let scratch_view: Option<&str> = cursor.scratch();
+    assert_eq!(Some("foo"), scratch_view);
+    let raw_view: &String = cursor.raw();
+    assert_eq!("raw", raw_view);
+
+    assert_eq!(vec![1, 2], buf);
+    assert_eq!("foo", scratch);
+}
+
+#[test]
+fn test_enum() {
+    let source = String::from("foo");
+    let node = Node::Branch {
+        text: &source,
+        children: &[1],
+    };
+    let text: &str = node.text();
+    let children: Option<&[u8]> = node.children();
+    assert_eq!("foo", text);
+    assert_eq!(Some(&[1][..]), children);
+}
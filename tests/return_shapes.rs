@@ -0,0 +1,112 @@
+#[macro_use]
+extern crate zyc_getset;
+
+#[derive(Getters, Default)]
+#[getset(get)]
+pub struct Plain {
+    #[getset(get(ret = "ref"))]
+    count: i32,
+    #[getset(get(ret = "ref"))]
+    name: String,
+    #[getset(get(ret = "ref"))]
+    parent: Option<String>,
+    #[getset(get(ret = "raw"))]
+    raw_name: String,
+    #[getset(get(ret = "copy"))]
+    cloned: Wrapper,
+    #[getset(get(ret = "option_ref"))]
+    label: Option<String>,
+    #[getset(get(ret = "option_ref"))]
+    port: Option<u16>,
+    #[getset(get(ret_type = "&str"))]
+    title: std::rc::Rc<str>,
+    #[getset(get(ret_type = "&u32"))]
+    retries: u32,
+    #[getset(get(ret = "copy", ret_type = "u64"))]
+    id: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Wrapper(u8);
+
+#[derive(Getters, Default)]
+#[getset(get(ret = "raw"))]
+pub struct Upstream {
+    count: i32,
+    name: String,
+    parent: Option<String>,
+}
+
+#[derive(Getters, Default)]
+#[getset(get(ret = "option_ref"))]
+pub struct OptionRefs {
+    parent: Option<String>,
+    port: Option<u16>,
+    count: i32,
+    name: String,
+}
+
+#[derive(Getters)]
+#[getset(get(ret = "raw"))]
+pub enum Event {
+    Created { id: u64, name: String },
+    Deleted { id: u64 },
+}
+
+#[test]
+fn test_ret_shapes() {
+    let val = Plain::default();
+    let count: &i32 = val.count();
+    let name: &str = val.name();
+    let parent: &Option<String> = val.parent();
+    let raw_name: &String = val.raw_name();
+    let cloned: Wrapper = val.cloned();
+    let label: Option<&String> = val.label();
+    let port: Option<&u16> = val.port();
+    let title: &str = val.title();
+    let retries: &u32 = val.retries();
+    let id: u64 = val.id();
+
+    assert_eq!(&0, count);
+    assert_eq!("", name);
+    assert_eq!(&None, parent);
+    assert!(raw_name.is_empty());
+    assert_eq!(Wrapper(0), cloned);
+    assert_eq!(None, label);
+    assert_eq!(None, port);
+    assert_eq!("", title);
+    assert_eq!(&0, retries);
+    assert_eq!(0, id);
+}
+
+#[test]
+fn test_struct_level() {
+    let val = Upstream::default();
+    let count: &i32 = val.count();
+    let name: &String = val.name();
+    let parent: &Option<String> = val.parent();
+    assert_eq!((&0, "", &None), (count, name.as_str(), parent));
+}
+
+#[test]
+fn test_struct_level_option_ref() {
+    let val = OptionRefs::default();
+    let parent: Option<&String> = val.parent();
+    let port: Option<&u16> = val.port();
+    // 非 Option 字段使用默认的返回形式
+    let count: i32 = val.count();
+    let name: &str = val.name();
+    assert_eq!((None, None, 0, ""), (parent, port, count, name));
+}
+
+#[test]
+fn test_enum() {
+    let val = Event::Created {
+        id: 1,
+        name: "foo".to_string(),
+    };
+    let id: &u64 = val.id();
+    let name: Option<&String> = val.name();
+    assert_eq!(&1, id);
+    assert_eq!(Some(&"foo".to_string()), name);
+}
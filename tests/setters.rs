@@ -21,17 +21,17 @@ mod submodule {
             /// This field is used for testing chaining.
             #[set = "pub"]
             second_public_accessible: bool,
-            // /// A doc comment.
-            // #[set = "pub(crate)"]
-            // crate_accessible: usize,
+            /// A doc comment.
+            #[set = "pub(crate)"]
+            crate_accessible: usize,
 
-            // /// A doc comment.
-            // #[set = "pub(super)"]
-            // super_accessible: usize,
+            /// A doc comment.
+            #[set = "pub(super)"]
+            super_accessible: usize,
 
-            // /// A doc comment.
-            // #[set = "pub(in super::other)"]
-            // scope_accessible: usize,
+            /// A doc comment.
+            #[set = "pub(in super::other)"]
+            scope_accessible: usize,
         }
 
         #[derive(Setters, Default)]
@@ -44,17 +44,17 @@ mod submodule {
             /// A doc comment.
             #[set = "pub"]
             public_accessible: T,
-            // /// A doc comment.
-            // #[set = "pub(crate)"]
-            // crate_accessible: usize,
+            /// A doc comment.
+            #[set = "pub(crate)"]
+            crate_accessible: usize,
 
-            // /// A doc comment.
-            // #[set = "pub(super)"]
-            // super_accessible: usize,
+            /// A doc comment.
+            #[set = "pub(super)"]
+            super_accessible: usize,
 
-            // /// A doc comment.
-            // #[set = "pub(in super::other)"]
-            // scope_accessible: usize,
+            /// A doc comment.
+            #[set = "pub(in super::other)"]
+            scope_accessible: usize,
         }
 
         #[derive(Setters, Default)]
@@ -70,17 +70,17 @@ mod submodule {
             /// A doc comment.
             #[set = "pub"]
             public_accessible: T,
-            // /// A doc comment.
-            // #[set = "pub(crate)"]
-            // crate_accessible: usize,
+            /// A doc comment.
+            #[set = "pub(crate)"]
+            crate_accessible: usize,
 
-            // /// A doc comment.
-            // #[set = "pub(super)"]
-            // super_accessible: usize,
+            /// A doc comment.
+            #[set = "pub(super)"]
+            super_accessible: usize,
 
-            // /// A doc comment.
-            // #[set = "pub(in super::other)"]
-            // scope_accessible: usize,
+            /// A doc comment.
+            #[set = "pub(in super::other)"]
+            scope_accessible: usize,
         }
 
         #[test]
@@ -93,13 +93,49 @@ mod submodule {
         fn test_generic() {
             let mut val = Generic::default();
             val.set_private_accessible(1);
+            val.set_crate_accessible(21)
+                .set_super_accessible(22)
+                .set_scope_accessible(23);
+            assert_eq!(
+                (21, 22, 23),
+                (
+                    val.crate_accessible,
+                    val.super_accessible,
+                    val.scope_accessible
+                )
+            );
         }
 
         #[test]
         fn test_where() {
             let mut val = Where::default();
             val.set_private_accessible(1);
+            val.set_crate_accessible(21)
+                .set_super_accessible(22)
+                .set_scope_accessible(23);
+            assert_eq!(
+                (21, 22, 23),
+                (
+                    val.crate_accessible,
+                    val.super_accessible,
+                    val.scope_accessible
+                )
+            );
         }
+
+        #[test]
+        fn test_restricted_plain() {
+            let mut val = Plain::default();
+            val.set_crate_accessible(1);
+            val.set_super_accessible(1);
+            val.set_scope_accessible(1);
+        }
+    }
+
+    #[test]
+    fn test_super_plain() {
+        let mut val = other::Plain::default();
+        val.set_super_accessible(1);
     }
 }
 
@@ -127,3 +163,9 @@ fn test_chaining() {
     val.set_public_accessible(1)
         .set_second_public_accessible(true);
 }
+
+#[test]
+fn test_crate_plain() {
+    let mut val = Plain::default();
+    val.set_crate_accessible(1);
+}
@@ -0,0 +1,143 @@
+#[macro_use]
+extern crate zyc_getset;
+
+use std::rc::{self, Rc};
+use std::sync::{self, Arc, Weak};
+
+pub trait Handler {
+    fn handle(&self) -> u32;
+}
+
+pub struct Double;
+
+impl Handler for Double {
+    fn handle(&self) -> u32 {
+        2
+    }
+}
+
+#[derive(Getters)]
+#[getset(get(smart_ptr))]
+pub struct Plain {
+    config: Arc<String>,
+    shared: Rc<Vec<u32>>,
+    handler: Box<dyn Handler>,
+    fallback: Option<Box<dyn Handler>>,
+    parent: Option<Arc<String>>,
+    owner: rc::Weak<String>,
+    sync_owner: sync::Weak<String>,
+    maybe_owner: Option<rc::Weak<String>>,
+}
+
+#[derive(Getters)]
+#[getset(get)]
+pub struct Opaque {
+    config: Arc<String>,
+    handler: Box<dyn Handler>,
+}
+
+#[derive(Getters)]
+pub struct Imported {
+    #[getset(get(smart_ptr, upgrade = "Arc"))]
+    owner: Weak<String>,
+    #[getset(get(smart_ptr, upgrade = "Rc"))]
+    maybe_owner: Option<rc::Weak<String>>,
+}
+
+#[derive(Getters)]
+#[getset(get(smart_ptr))]
+pub enum Node {
+    Leaf { parent: rc::Weak<String> },
+    Root { name: Rc<String> },
+}
+
+#[test]
+fn test_smart_ptr() {
+    let owner = Rc::new("owner".to_string());
+    let sync_owner = Arc::new("sync owner".to_string());
+    let val = Plain {
+        config: Arc::new("config".to_string()),
+        shared: Rc::new(vec![1, 2]),
+        handler: Box::new(Double),
+        fallback: None,
+        parent: Some(Arc::new("parent".to_string())),
+        owner: Rc::downgrade(&owner),
+        sync_owner: Arc::downgrade(&sync_owner),
+        maybe_owner: None,
+    };
+
+    let config: &String = val.config();
+    let shared: &Vec<u32> = val.shared();
+    let handler: &dyn Handler = val.handler();
+    assert_eq!("config", config);
+    assert_eq!(&vec![1, 2], shared);
+    assert_eq!(2, handler.handle());
+    assert!(val.fallback().is_none());
+    assert_eq!(Some(&"parent".to_string()), val.parent());
+
+    let upgraded: Option<Arc<String>> = val.upgrade_sync_owner();
+    assert_eq!(Some(sync_owner), upgraded);
+    assert_eq!(Some(owner.clone()), val.upgrade_owner());
+    assert_eq!(None, val.upgrade_maybe_owner());
+
+    drop(owner);
+    assert_eq!(None, val.upgrade_owner());
+}
+
+#[test]
+fn test_box_dyn_option() {
+    let val = Plain {
+        config: Arc::new(String::new()),
+        shared: Rc::new(Vec::new()),
+        handler: Box::new(Double),
+        fallback: Some(Box::new(Double)),
+        parent: None,
+        owner: rc::Weak::new(),
+        sync_owner: sync::Weak::new(),
+        maybe_owner: Some(rc::Weak::new()),
+    };
+    let fallback: Option<&dyn Handler> = val.fallback();
+    assert_eq!(Some(2), fallback.map(Handler::handle));
+    assert_eq!(None, val.upgrade_maybe_owner());
+}
+
+#[test]
+fn test_without_smart_ptr() {
+    let val = Opaque {
+        config: Arc::new("config".to_string()),
+        handler: Box::new(Double),
+    };
+    let config: &Arc<String> = val.config();
+    let handler: &dyn Handler = val.handler();
+    assert_eq!("config", config.as_str());
+    assert_eq!(2, handler.handle());
+}
+
+#[test]
+fn test_enum() {
+    let root = Rc::new("root".to_string());
+    let val = Node::Leaf {
+        parent: Rc::downgrade(&root),
+    };
+    assert_eq!(Some(root), val.upgrade_parent());
+    assert_eq!(None, val.name());
+
+    let val = Node::Root {
+        name: Rc::new("root".to_string()),
+    };
+    assert_eq!(None, val.upgrade_parent());
+    assert_eq!(Some(&"root".to_string()), val.name());
+}
+
+#[test]
+fn test_upgrade_option() {
+    let owner = Arc::new("owner".to_string());
+    let maybe_owner = Rc::new("maybe owner".to_string());
+    let val = Imported {
+        owner: Arc::downgrade(&owner),
+        maybe_owner: Some(Rc::downgrade(&maybe_owner)),
+    };
+    let upgraded: Option<Arc<String>> = val.upgrade_owner();
+    assert_eq!(Some(owner), upgraded);
+    assert_eq!(Some(maybe_owner), val.upgrade_maybe_owner());
+}
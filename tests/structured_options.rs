@@ -0,0 +1,74 @@
+#[macro_use]
+extern crate zyc_getset;
+
+use crate::submodule::other::Plain;
+
+fn check_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        Err("must not be empty".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+mod submodule {
+    pub mod other {
+        #[derive(Getters, Setters, MutGetters, Default)]
+        #[getset(get(vis = pub(crate)), set(vis = pub(crate)))]
+        pub struct Plain {
+            crate_accessible: usize,
+            #[getset(get(vis = pub(super)), set(vis = pub(super)))]
+            super_accessible: usize,
+            #[getset(get(vis = pub(in super::other), with_prefix, name = scoped), get_mut(vis = "pub(in crate::submodule)"))]
+            scope_accessible: usize,
+            #[getset(get(vis = pub, with_prefix), set(vis = pub(crate), into))]
+            label: String,
+            #[getset(get, set(validate = crate::check_name, name = rename))]
+            name: String,
+        }
+
+        #[test]
+        fn test_scope() {
+            let val = Plain {
+                scope_accessible: 3,
+                ..Default::default()
+            };
+            assert_eq!(3, val.scoped());
+        }
+    }
+
+    #[test]
+    fn test_super() {
+        let mut val = other::Plain::default();
+        val.set_super_accessible(2);
+        *val.scope_accessible_mut() += 1;
+        assert_eq!(2, val.super_accessible());
+    }
+}
+
+#[derive(Getters, Default)]
+#[getset(get(with_prefix))]
+pub struct Prefixed {
+    id: u64,
+    #[getset(get(with_prefix, vis = "pub"))]
+    name: String,
+}
+
+#[test]
+fn test_structured() {
+    let mut val = Plain::default();
+    val.set_crate_accessible(1).set_label("foo");
+    val.try_rename("bar".to_string()).unwrap();
+    assert!(val.try_rename(String::new()).is_err());
+
+    assert_eq!(1, val.crate_accessible());
+    assert_eq!("foo", val.get_label());
+    assert_eq!("bar", val.name());
+}
+
+#[test]
+fn test_prefix_flag() {
+    let val = Prefixed::default();
+    assert_eq!(0, val.get_id());
+    assert_eq!("", val.get_name());
+}
@@ -0,0 +1,60 @@
+#[macro_use]
+extern crate zyc_getset;
+
+#[derive(Getters, Setters, MutGetters, Default)]
+pub struct UserId(
+    /// A doc comment.
+    #[getset(
+        get(name = "value"),
+        set(name = "set_value"),
+        get_mut(name = "value_mut")
+    )]
+    u64,
+);
+
+#[derive(Getters, Setters, MutGetters, Default)]
+#[getset(get, set, get_mut)]
+pub struct Meters(f64);
+
+#[derive(Getters, CloneGetters, Setters, Default)]
+#[getset(get, set)]
+pub struct Pair(
+    String,
+    Option<String>,
+    Option<u8>,
+    #[getset(get = "with_prefix")] usize,
+    #[getset(get_clone)] String,
+    #[getset(skip)] usize,
+);
+
+#[test]
+fn test_named_accessors() {
+    let mut val = UserId::default();
+    val.set_value(42);
+    assert_eq!(42, val.value());
+    *val.value_mut() += 1;
+    assert_eq!(43, val.value());
+}
+
+#[test]
+fn test_default_accessors() {
+    let mut val = Meters::default();
+    val.set_0(1.5);
+    *val._0_mut() += 1.0;
+    assert_eq!(2.5, val._0());
+}
+
+#[test]
+fn test_return_shapes() {
+    let mut val = Pair::default();
+    val.set_0("foo".to_string())
+        .set_1(Some("bar".to_string()))
+        .set_2(Some(3));
+    val.3 = 4;
+    assert_eq!("foo", val._0());
+    assert_eq!(Some("bar"), val._1());
+    assert_eq!(Some(3), val._2());
+    assert_eq!(4, val.get_3());
+    assert_eq!(String::new(), val._4());
+    assert_eq!(0, val.5);
+}
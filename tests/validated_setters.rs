@@ -0,0 +1,80 @@
+#[macro_use]
+extern crate zyc_getset;
+
+fn check_host(host: &str) -> Result<(), String> {
+    if host.contains(' ') {
+        Err("must not contain spaces".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Setters, Getters, Default, Debug)]
+#[getset(get, set)]
+pub struct Config {
+    #[getset(get, set(range = "1..=65535"))]
+    port: u32,
+    #[getset(get, set(non_empty, into))]
+    name: String,
+    #[getset(get, set(validate = "check_host", non_empty))]
+    host: String,
+    timeout: u64,
+}
+
+#[test]
+fn test_valid_values() {
+    let mut val = Config::default();
+    val.try_set_port(8080)
+        .unwrap()
+        .try_set_name("foo")
+        .unwrap()
+        .try_set_host("localhost".to_string())
+        .unwrap()
+        .set_timeout(3);
+    assert_eq!(8080, val.port());
+    assert_eq!("foo", val.name());
+    assert_eq!("localhost", val.host());
+    assert_eq!(3, val.timeout());
+}
+
+#[test]
+fn test_invalid_values() {
+    let mut val = Config::default();
+
+    let err = val.try_set_port(0).unwrap_err();
+    assert_eq!(
+        ConfigSetError::Port("must be in range 1..=65535".to_string()),
+        err
+    );
+    assert_eq!("port", err.field());
+
+    let err = val.try_set_name("").unwrap_err();
+    assert_eq!("name", err.field());
+    assert_eq!("must not be empty", err.reason());
+
+    let err = val.try_set_host("local host".to_string()).unwrap_err();
+    assert_eq!(
+        "invalid value for `host`: must not contain spaces",
+        err.to_string()
+    );
+
+    let err = val.try_set_host(String::new()).unwrap_err();
+    assert_eq!("must not be empty", err.reason());
+
+    assert_eq!(0, val.port());
+    assert_eq!("", val.name());
+}
+
+#[derive(Setters, Default, Debug)]
+pub struct Percent(#[getset(set(name = "set_value", range = "0.0..=100.0"))] f64);
+
+#[test]
+fn test_tuple_struct() {
+    let mut val = Percent::default();
+    assert!(val.try_set_value(50.0).is_ok());
+    let err: Box<dyn std::error::Error> = Box::new(val.try_set_value(101.0).unwrap_err());
+    assert_eq!(
+        "invalid value for `0`: must be in range 0.0..=100.0",
+        err.to_string()
+    );
+}
@@ -0,0 +1,80 @@
+#[macro_use]
+extern crate zyc_getset;
+
+#[derive(VariantHelpers, Debug, PartialEq)]
+#[getset(variant)]
+pub enum Request {
+    /// A plain ping.
+    Ping,
+    HttpRequest(String),
+    Move(i32, i32),
+    Rename {
+        from: String,
+        to: String,
+    },
+    #[getset(skip)]
+    Skipped(u8),
+    #[getset(variant = "pub(crate)")]
+    Internal {
+        id: u64,
+    },
+}
+
+#[test]
+fn test_is() {
+    assert!(Request::Ping.is_ping());
+    assert!(!Request::Ping.is_http_request());
+    assert!(Request::Move(1, 2).is_move());
+    assert!(Request::Internal { id: 1 }.is_internal());
+}
+
+#[test]
+fn test_as() {
+    let val = Request::HttpRequest("foo".to_string());
+    assert_eq!(Some(&"foo".to_string()), val.as_http_request());
+    assert_eq!(None, val.as_move());
+
+    let val = Request::Move(1, 2);
+    assert_eq!(Some((&1, &2)), val.as_move());
+
+    let val = Request::Rename {
+        from: "a".to_string(),
+        to: "b".to_string(),
+    };
+    assert_eq!(Some((&"a".to_string(), &"b".to_string())), val.as_rename());
+    assert_eq!(Some(&1), Request::Internal { id: 1 }.as_internal());
+}
+
+#[test]
+fn test_as_mut() {
+    let mut val = Request::Move(1, 2);
+    if let Some((x, y)) = val.as_move_mut() {
+        *x += 10;
+        *y += 20;
+    }
+    assert_eq!(Request::Move(11, 22), val);
+    assert_eq!(None, val.as_http_request_mut());
+}
+
+#[test]
+fn test_into() {
+    let val = Request::HttpRequest("foo".to_string());
+    assert_eq!(Ok("foo".to_string()), val.into_http_request());
+    assert_eq!(Err(Request::Ping), Request::Ping.into_move());
+    assert_eq!(Ok((1, 2)), Request::Move(1, 2).into_move());
+}
+
+#[derive(VariantHelpers, Debug, PartialEq)]
+#[getset(variant)]
+pub enum Single {
+    Only(u8),
+}
+
+#[test]
+fn test_single_variant() {
+    let mut val = Single::Only(1);
+    assert!(val.is_only());
+    *val.as_only_mut().unwrap() += 1;
+    assert_eq!(Some(&2), val.as_only());
+    assert_eq!(Ok(2), val.into_only());
+}
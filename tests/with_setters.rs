@@ -0,0 +1,89 @@
+#[macro_use]
+extern crate zyc_getset;
+
+#[derive(WithSetters, Getters, Default)]
+#[getset(get, with)]
+pub struct Plain {
+    name: String,
+    port: u16,
+    #[getset(get, with(into))]
+    host: String,
+}
+
+#[derive(WithSetters, Getters, Default, Clone)]
+#[getset(get, with(clone))]
+pub struct Persistent {
+    name: String,
+    port: u16,
+}
+
+#[derive(WithSetters, Getters, Default)]
+#[getset(get, with = "pub(crate)")]
+pub struct Generic<T: Copy + Default> {
+    value: T,
+}
+
+#[derive(WithSetters, Getters)]
+#[getset(get, with)]
+pub enum Shape {
+    Circle { label: String, radius: f64 },
+    Square { label: String },
+}
+
+#[test]
+fn test_with() {
+    let val = Plain::default()
+        .with_name("foo".to_string())
+        .with_port(8080)
+        .with_host("localhost");
+    assert_eq!("foo", val.name());
+    assert_eq!(8080, val.port());
+    assert_eq!("localhost", val.host());
+}
+
+#[test]
+fn test_with_clone() {
+    let base = Persistent::default().with_port(80);
+    let changed = base.with_name("foo".to_string());
+    assert_eq!("", base.name());
+    assert_eq!(80, base.port());
+    assert_eq!("foo", changed.name());
+    assert_eq!(80, changed.port());
+}
+
+#[test]
+fn test_with_generic() {
+    let val = Generic::<u32>::default().with_value(3);
+    assert_eq!(3, val.value());
+}
+
+#[test]
+fn test_with_enum() {
+    let val = Shape::Square {
+        label: String::new(),
+    }
+    .with_label("foo".to_string());
+    assert_eq!("foo", val.label());
+    assert_eq!(None, val.radius());
+}
+
+#[derive(WithSetters, Getters, Default, Clone)]
+#[getset(get, with)]
+pub struct SelfNamed {
+    this: u32,
+}
+
+#[derive(WithSetters, Getters, Default, Clone)]
+#[getset(get, with(clone))]
+pub struct ClonedSelfNamed {
+    this: u32,
+}
+
+#[test]
+fn test_with_field_named_this() {
+    let val = SelfNamed::default().with_this(1);
+    assert_eq!(1, val.this());
+    let val = ClonedSelfNamed::default();
+    assert_eq!(2, val.with_this(2).this());
+    assert_eq!(0, val.this());
+}